@@ -2,12 +2,28 @@
 
 #![warn(missing_docs)]
 
-use winit::keyboard::{Key, NativeKey, NativeKeyCode, PhysicalKey};
+use std::cell::RefCell;
+
+use thiserror::Error;
+#[cfg(feature = "winit")]
+use winit::event::Modifiers;
+#[cfg(feature = "winit")]
+use winit::keyboard::{Key, ModifiersKeyState, ModifiersState, NativeKeyCode, PhysicalKey};
 
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "macos")]
 mod macos;
+#[cfg(feature = "winit")]
+mod parse;
+mod platform;
+#[cfg(feature = "winit")]
+mod qwerty;
+#[cfg(feature = "winit")]
+mod sequence;
+#[cfg(feature = "winit")]
+mod shortcut;
+mod style;
 #[cfg(target_arch = "wasm32")]
 mod web;
 #[cfg(windows)]
@@ -22,57 +38,420 @@ use web as os;
 #[cfg(windows)]
 use windows as os;
 
+#[cfg(all(target_os = "linux", any(feature = "x11", feature = "wayland")))]
+pub use linux::LayoutWatcher;
+#[cfg(all(target_os = "linux", feature = "x11"))]
+pub use linux::X11KeyboardDevice;
+#[cfg(target_os = "linux")]
+pub use linux::{
+    last_backend_diagnostics, Backend, BackendAttempt, BackendDiagnostics, KeymapError,
+    BACKEND_ENV_VAR,
+};
+pub use platform::Platform;
+#[cfg(feature = "winit")]
+pub use sequence::{KeySequence, KeySequenceMatcher, SequenceMatch};
+#[cfg(feature = "winit")]
+pub use shortcut::{Shortcut, ShortcutKey, ShortcutParseError, ShortcutParseErrorKind};
+pub use style::{NameStyle, ShortcutStyle};
+#[cfg(all(target_os = "linux", feature = "wayland"))]
+pub use wayland_client;
+#[cfg(all(target_os = "linux", feature = "x11"))]
+pub use xcb;
+#[cfg(target_os = "linux")]
+pub use xkbcommon;
+
+/// Error returned when the user's keyboard layout cannot be queried.
+#[derive(Error, Debug, Clone)]
+pub enum KeyNamesError {
+    /// Unable to get the keymap from X11 or Wayland.
+    #[cfg(target_os = "linux")]
+    #[error("failed to get keymap: {0}")]
+    Keymap(std::sync::Arc<KeymapError>),
+}
+
+/// Shift level of a key, which determines which modifiers must be held to
+/// produce a particular symbol.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Level {
+    /// No modifiers.
+    Base,
+    /// <key>Shift</key>.
+    Shift,
+    /// <key>AltGr</key> (also called <key>Option</key> on macOS, or level 3).
+    AltGr,
+    /// <key>Shift</key> + <key>AltGr</key> (also called level 4).
+    ShiftAltGr,
+}
+
+impl Level {
+    /// All levels, in order.
+    pub const ALL: [Level; 4] = [Level::Base, Level::Shift, Level::AltGr, Level::ShiftAltGr];
+
+    /// Returns whether <key>Shift</key> is held at this level.
+    pub fn shift(self) -> bool {
+        matches!(self, Level::Shift | Level::ShiftAltGr)
+    }
+
+    /// Returns whether <key>AltGr</key> is held at this level.
+    pub fn altgr(self) -> bool {
+        matches!(self, Level::AltGr | Level::ShiftAltGr)
+    }
+}
+
+/// Keyboard layout used to produce physical key names.
+///
+/// On Linux, this owns an XKB keymap obtained from one of the backends listed
+/// in [`Backend`], and can be constructed explicitly using
+/// [`Layout::from_x11()`], [`Layout::from_wayland()`], or
+/// [`Layout::from_backend()`]. On Windows, this represents the user's current
+/// keyboard layout. On macOS and web, this represents a hard-coded US QWERTY
+/// layout.
+///
+/// The free functions in this crate, such as [`physical_key_name()`], use a
+/// default per-thread layout constructed using [`Layout::new()`].
+#[derive(Debug, Clone)]
+pub struct Layout(os::Layout);
+
+impl Layout {
+    /// Queries the user's current keyboard layout.
+    ///
+    /// On Linux, this tries each backend in [`Backend::DEFAULT_ORDER`] that is
+    /// enabled by its cargo feature: Wayland if the `wayland` feature is
+    /// enabled and then X11 if the `x11` feature is enabled. It returns the
+    /// first keymap obtained, or an error if every backend fails. The evdev
+    /// and static backends, which don't need a display server, are not tried
+    /// by default.
+    ///
+    /// The [`BACKEND_ENV_VAR`] (`KEY_NAMES_BACKEND`) environment variable
+    /// overrides the order with a comma-separated list of backend names, such
+    /// as `x11,wayland` or `wayland,x11,evdev,static`. Names are `wayland`,
+    /// `x11`, `evdev` (the default XKB keymap, configured by the
+    /// `XKB_DEFAULT_*` environment variables), and `static` (a US QWERTY keymap
+    /// embedded in the crate). Listing a backend whose feature is disabled
+    /// makes it fail with [`KeymapError::BackendDisabled`]. Use
+    /// [`Layout::new_with_diagnostics()`] to find out which backend was used
+    /// and why the others failed.
+    ///
+    /// On all other platforms, this never returns an error.
+    pub fn new() -> Result<Self, KeyNamesError> {
+        os::Layout::new().map(Self)
+    }
+
+    /// Returns a human-friendly name for a key using this layout, given its
+    /// platform-specific scancode, or `SC{n}` if the key has no known name.
+    ///
+    /// On Linux, scancodes are evdev scancodes (which are 8 less than X11
+    /// keycodes). On Windows, they are PS/2 scancodes, with `0xE0` in the high
+    /// byte for extended keys. On macOS, they are virtual key codes. On web,
+    /// scancodes are not available.
+    ///
+    /// This does not require the `winit` feature. When it is enabled, this is
+    /// the same as [`Layout::physical_key_name()`] for the key with the
+    /// corresponding scancode. On macOS, key names come from a hard-coded
+    /// table of winit key codes, so without the `winit` feature every key is
+    /// named `SC{n}`.
+    pub fn scancode_name(&self, scancode: u32) -> String {
+        self.0
+            .try_scancode_name(scancode)
+            .unwrap_or_else(|| format!("SC{scancode}"))
+    }
+
+    /// Returns the scancode and level that produce a character using this
+    /// layout, or `None` if no key produces it.
+    ///
+    /// Keys that require fewer modifiers are preferred. See
+    /// [`Layout::scancode_name()`] for the meaning of scancodes on each
+    /// platform. On macOS, this requires the `winit` feature and otherwise
+    /// always returns `None`.
+    pub fn scancode_for_char(&self, c: char) -> Option<(u32, Level)> {
+        self.0.scancode_for_char(c)
+    }
+
+    /// Returns a human-friendly name for a physical key using this layout.
+    #[cfg(feature = "winit")]
+    pub fn physical_key_name(&self, physical_key: PhysicalKey) -> String {
+        self.0
+            .try_physical_key_name(physical_key)
+            .unwrap_or_else(|| unknown_physical_key_name(physical_key))
+    }
+
+    /// Returns the physical key and level that produce a character using this
+    /// layout, or `None` if no key produces it.
+    ///
+    /// Keys that require fewer modifiers are preferred. For example, `'z'`
+    /// produces `KeyY` at [`Level::Base`] on a German layout, and `'Z'`
+    /// produces `KeyY` at [`Level::Shift`].
+    #[cfg(feature = "winit")]
+    pub fn physical_key_for_char(&self, c: char) -> Option<(PhysicalKey, Level)> {
+        self.0.physical_key_for_char(c)
+    }
+
+    /// Parses a physical key name produced by [`Layout::physical_key_name()`].
+    ///
+    /// This also accepts the `Debug` name of any
+    /// [`KeyCode`](winit::keyboard::KeyCode), such as `"KeyA"`. If there is no
+    /// exact match, names are matched ignoring case and the spaces between
+    /// words, so that "page up" and "pageup" both match "PageUp". Returns
+    /// `None` if no key has the given name.
+    #[cfg(feature = "winit")]
+    pub fn parse_physical_key(&self, s: &str) -> Option<PhysicalKey> {
+        let candidates = parse::KEY_CODES
+            .iter()
+            .map(|&key_code| PhysicalKey::Code(key_code))
+            .chain(self.0.unidentified_physical_keys());
+        parse::parse_physical_key(candidates, |pk| self.physical_key_name(pk), s)
+    }
+
+    /// Returns a human-friendly name for a virtual key. This is the same as
+    /// [`key_name()`].
+    #[cfg(feature = "winit")]
+    pub fn key_name(&self, key: Key) -> String {
+        key_name(key)
+    }
+
+    /// Parses a virtual key name. This is the same as [`parse_key()`].
+    #[cfg(feature = "winit")]
+    pub fn parse_key(&self, s: &str) -> Option<Key> {
+        parse_key(s)
+    }
+
+    /// Returns a string representing modifiers using the OS's conventional
+    /// names and ordering. This is the same as [`modifiers_prefix_string()`].
+    #[cfg(feature = "winit")]
+    pub fn modifiers_prefix_string(&self, mods: impl AsModifiersState) -> String {
+        modifiers_prefix_string(mods)
+    }
+}
+
+thread_local! {
+    static DEFAULT_LAYOUT: RefCell<Option<Result<Layout, KeyNamesError>>> = const { RefCell::new(None) };
+}
+
+fn with_default_layout<R>(f: impl FnOnce(&Result<Layout, KeyNamesError>) -> R) -> R {
+    DEFAULT_LAYOUT.with_borrow_mut(|layout| f(layout.get_or_insert_with(Layout::new)))
+}
+
+/// Sets the layout used by the free functions in this crate, such as
+/// [`physical_key_name()`], on the current thread.
+///
+/// By default, each thread uses a layout constructed using [`Layout::new()`]
+/// the first time it is needed.
+pub fn set_default_layout(layout: Layout) {
+    DEFAULT_LAYOUT.set(Some(Ok(layout)));
+}
+
 /// OS's conventional modifiers order, represented as an ASCII string containing
 /// the characters `csam` for `CTRL`, `SHIFT`, `ALT`, and `META`/`LOGO`
 /// respectively in some order.
-pub const MODIFIERS_ORDER: &str = os::MODIFIERS_ORDER;
+pub const MODIFIERS_ORDER: &str = Platform::NATIVE.modifiers_order();
 
 /// OS's conventional name for the <key>Ctrl</key> modifier.
 pub const CTRL_STR: &str = "Ctrl";
 /// OS's conventional name for the <key>Shift</key> modifier.
 pub const SHIFT_STR: &str = "Shift";
 /// OS's conventional name for the <key>Alt</key> modifier.
-pub const ALT_STR: &str = os::ALT_STR;
+pub const ALT_STR: &str = Platform::NATIVE.alt_str();
 /// OS's conventional name for the logo modifier.
-pub const LOGO_STR: &str = os::LOGO_STR;
+pub const LOGO_STR: &str = Platform::NATIVE.logo_str();
+
+#[cfg(feature = "winit")]
+/// Set of held modifiers, represented by either winit's [`ModifiersState`] or
+/// [`Modifiers`].
+pub trait AsModifiersState {
+    /// Returns the set of held modifiers.
+    fn as_modifiers_state(&self) -> ModifiersState;
+}
+#[cfg(feature = "winit")]
+impl AsModifiersState for ModifiersState {
+    fn as_modifiers_state(&self) -> ModifiersState {
+        *self
+    }
+}
+#[cfg(feature = "winit")]
+impl AsModifiersState for Modifiers {
+    fn as_modifiers_state(&self) -> ModifiersState {
+        self.state()
+    }
+}
 
 /// Returns a string representing modifiers using the OS's conventional names
 /// and ordering. For example, on Windows this function might produce "Ctrl +
 /// Shift + Alt + Win + " while on macOS it might produce "Ctrl + Option + Shift
 /// + Cmd + ".
+///
+/// This is the only modifier formatter that doesn't require the `winit`
+/// feature. When it is enabled, prefer [`modifiers_prefix_string()`], which
+/// takes winit's modifiers directly so that the arguments can't be mixed up.
 pub fn mods_prefix_string(shift: bool, ctrl: bool, alt: bool, logo: bool) -> String {
-    let mut ret = String::new();
-    for ch in MODIFIERS_ORDER.chars() {
-        match ch {
-            's' if shift => {
-                ret += SHIFT_STR;
-                ret += " + ";
-            }
-            'c' if ctrl => {
-                ret += CTRL_STR;
-                ret += " + ";
-            }
-            'a' if alt => {
-                ret += ALT_STR;
-                ret += " + ";
-            }
-            'm' if logo => {
-                ret += LOGO_STR;
-                ret += " + ";
-            }
-            _ => (),
+    let style = ShortcutStyle::NATIVE;
+    let held = style.held_modifiers(|ch| match ch {
+        's' => shift,
+        'c' => ctrl,
+        'a' => alt,
+        'm' => logo,
+        _ => false,
+    });
+    let names: Vec<&str> = held.into_iter().map(|(_, name)| name).collect();
+    style.prefix_string(&names)
+}
+
+#[cfg(feature = "winit")]
+/// Returns a string representing modifiers using the OS's conventional names
+/// and ordering, such as "Ctrl + Shift + ". Each modifier name is followed by
+/// " + ", so the key name can be appended directly.
+pub fn modifiers_prefix_string(mods: impl AsModifiersState) -> String {
+    ShortcutStyle::NATIVE.modifiers_prefix_string(mods)
+}
+
+#[cfg(feature = "winit")]
+/// Returns the names of the held modifiers using the OS's conventional names
+/// and ordering, such as `["Ctrl", "Shift"]`.
+pub fn modifier_names(mods: impl AsModifiersState) -> Vec<&'static str> {
+    ShortcutStyle::NATIVE.modifier_names(mods)
+}
+
+#[cfg(feature = "winit")]
+/// Set of held modifiers, along with which side of the keyboard each one is
+/// held on.
+///
+/// This can be constructed from winit's [`Modifiers`], which tracks which
+/// modifier keys are pressed on platforms that support it, or from
+/// [`ModifiersState`], which has no side information.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SidedModifiers {
+    /// Held modifiers.
+    pub state: ModifiersState,
+    /// Modifiers held using the key on the left side of the keyboard.
+    pub left: ModifiersState,
+    /// Modifiers held using the key on the right side of the keyboard.
+    pub right: ModifiersState,
+}
+#[cfg(feature = "winit")]
+impl From<ModifiersState> for SidedModifiers {
+    fn from(state: ModifiersState) -> Self {
+        SidedModifiers {
+            state,
+            ..Default::default()
+        }
+    }
+}
+#[cfg(feature = "winit")]
+impl From<Modifiers> for SidedModifiers {
+    fn from(mods: Modifiers) -> Self {
+        let pressed = |key_state| key_state == ModifiersKeyState::Pressed;
+        let mut left = ModifiersState::empty();
+        left.set(ModifiersState::SHIFT, pressed(mods.lshift_state()));
+        left.set(ModifiersState::CONTROL, pressed(mods.lcontrol_state()));
+        left.set(ModifiersState::ALT, pressed(mods.lalt_state()));
+        left.set(ModifiersState::SUPER, pressed(mods.lsuper_state()));
+        let mut right = ModifiersState::empty();
+        right.set(ModifiersState::SHIFT, pressed(mods.rshift_state()));
+        right.set(ModifiersState::CONTROL, pressed(mods.rcontrol_state()));
+        right.set(ModifiersState::ALT, pressed(mods.ralt_state()));
+        right.set(ModifiersState::SUPER, pressed(mods.rsuper_state()));
+        SidedModifiers {
+            state: mods.state(),
+            left,
+            right,
         }
     }
-    ret
+}
+#[cfg(feature = "winit")]
+impl AsModifiersState for SidedModifiers {
+    fn as_modifiers_state(&self) -> ModifiersState {
+        self.state
+    }
 }
 
+#[cfg(feature = "winit")]
+/// Returns a string representing modifiers using the OS's conventional names
+/// and ordering, such as "Right Control + Shift + ". Modifiers held on only one
+/// side of the keyboard are given side-specific names.
+///
+/// See [`sided_modifier_names()`].
+pub fn sided_modifiers_prefix_string(mods: impl Into<SidedModifiers>) -> String {
+    ShortcutStyle::NATIVE.sided_modifiers_prefix_string(mods)
+}
+
+#[cfg(feature = "winit")]
+/// Returns the names of the held modifiers using the OS's conventional names
+/// and ordering, such as `["Right Control", "Shift"]`.
+///
+/// Modifiers held on only one side of the keyboard are named after the key on
+/// that side, as in [`physical_key_name()`], such as "Left Super" on Linux or
+/// "Right Option" on macOS. On macOS the left keys have no side in their names,
+/// so modifiers held on the left use their conventional names, such as
+/// [`CTRL_STR`]. These names are accepted when parsing a [`Shortcut`].
+pub fn sided_modifier_names(mods: impl Into<SidedModifiers>) -> Vec<String> {
+    ShortcutStyle::NATIVE.sided_modifier_names(mods)
+}
+
+/// Returns a human-friendly name for a key using the operating system's API,
+/// given its platform-specific scancode, or `SC{n}` if the key has no known
+/// name or the keyboard layout cannot be queried.
+///
+/// This does not require the `winit` feature, except on macOS. See
+/// [`Layout::scancode_name()`].
+pub fn scancode_name(scancode: u32) -> String {
+    with_default_layout(|layout| match layout {
+        Ok(layout) => layout.scancode_name(scancode),
+        Err(_) => format!("SC{scancode}"),
+    })
+}
+
+/// Returns the scancode and level that produce a character on the user's
+/// keyboard layout, or `None` if no key produces it or the keyboard layout
+/// cannot be queried.
+///
+/// This does not require the `winit` feature, except on macOS. See
+/// [`Layout::scancode_for_char()`].
+pub fn scancode_for_char(c: char) -> Option<(u32, Level)> {
+    with_default_layout(|layout| layout.as_ref().ok()?.scancode_for_char(c))
+}
+
+#[cfg(feature = "winit")]
 /// Returns a human-friendly name for a physical key using the operating
 /// system's API when possible.
 ///
 /// On Windows and Linux, this queries the user's keyboard layout. On macOS and
 /// web, hard-coded key names are used.
+///
+/// If the keyboard layout cannot be queried (such as on Linux when neither X11
+/// nor Wayland is available), this falls back to
+/// [`fallback_physical_key_name()`]. Use [`try_physical_key_name()`] to detect
+/// this case.
 pub fn physical_key_name(physical_key: PhysicalKey) -> String {
-    os::try_physical_key_name(physical_key).unwrap_or_else(|| match physical_key {
+    try_physical_key_name(physical_key).unwrap_or_else(|_| fallback_physical_key_name(physical_key))
+}
+
+#[cfg(feature = "winit")]
+/// Returns a human-friendly name for a physical key using the operating
+/// system's API when possible, or an error if the user's keyboard layout
+/// cannot be queried.
+///
+/// On Linux, this returns an error if neither X11 nor Wayland is available,
+/// such as in a headless CI job, an SSH session, or a TTY application. On all
+/// other platforms, this never returns an error.
+pub fn try_physical_key_name(physical_key: PhysicalKey) -> Result<String, KeyNamesError> {
+    with_default_layout(|layout| match layout {
+        Ok(layout) => Ok(layout.physical_key_name(physical_key)),
+        Err(e) => Err(e.clone()),
+    })
+}
+
+#[cfg(feature = "winit")]
+/// Returns a human-friendly name for a physical key based on the US QWERTY
+/// layout, without querying the operating system.
+///
+/// This never fails and is suitable as a fallback when
+/// [`try_physical_key_name()`] returns an error.
+pub fn fallback_physical_key_name(physical_key: PhysicalKey) -> String {
+    Platform::NATIVE.physical_key_name(physical_key)
+}
+
+#[cfg(feature = "winit")]
+fn unknown_physical_key_name(physical_key: PhysicalKey) -> String {
+    match physical_key {
         PhysicalKey::Code(key_code) => format!("{key_code:?}"),
         PhysicalKey::Unidentified(native_key_code) => match native_key_code {
             NativeKeyCode::Unidentified => "<unknown>".to_string(),
@@ -81,29 +460,76 @@ pub fn physical_key_name(physical_key: PhysicalKey) -> String {
             NativeKeyCode::Windows(sc) => format!("SC{sc}"),
             NativeKeyCode::Xkb(sc) => format!("SC{sc}"),
         },
+    }
+}
+
+#[cfg(feature = "winit")]
+/// Returns the physical key and level that produce a character on the user's
+/// keyboard layout, or `None` if no key produces it.
+///
+/// On Windows and Linux, this queries the user's keyboard layout. On macOS and
+/// web, a hard-coded US QWERTY layout is used. If the keyboard layout cannot be
+/// queried, this also falls back to the US QWERTY layout.
+///
+/// See [`Layout::physical_key_for_char()`].
+pub fn physical_key_for_char(c: char) -> Option<(PhysicalKey, Level)> {
+    with_default_layout(|layout| match layout {
+        Ok(layout) => layout.physical_key_for_char(c),
+        Err(_) => qwerty::physical_key_for_char(
+            |physical_key| Platform::NATIVE.hardcoded_physical_key_name(physical_key),
+            c,
+        ),
     })
 }
 
+#[cfg(feature = "winit")]
+/// Parses a physical key name produced by [`physical_key_name()`] back into
+/// a physical key, or returns `None` if no key has the given name.
+///
+/// This accepts names from the user's keyboard layout, the `SC{n}` format for
+/// keys with no known name, `"<unknown>"`, and the `Debug` name of any
+/// [`KeyCode`](winit::keyboard::KeyCode), such as `"KeyA"`. If there is no
+/// exact match, names are matched ignoring case and the spaces between words,
+/// so that "page up" and "pageup" both match "PageUp".
+///
+/// If the keyboard layout cannot be queried, names from
+/// [`fallback_physical_key_name()`] are used instead.
+///
+/// See [`Layout::parse_physical_key()`].
+pub fn parse_physical_key(s: &str) -> Option<PhysicalKey> {
+    with_default_layout(|layout| match layout {
+        Ok(layout) => layout.parse_physical_key(s),
+        Err(_) => {
+            let candidates = parse::KEY_CODES.map(PhysicalKey::Code);
+            parse::parse_physical_key(candidates, fallback_physical_key_name, s)
+        }
+    })
+}
+
+#[cfg(feature = "winit")]
 /// Returns a human-friendly name for a virtual key.
 ///
 /// Letters are uppercased and some special keys are given OS-specific names
 /// (such as "Win" on Windows vs. "Super" on Linux vs. "Command" on macOS).
 pub fn key_name(key: Key) -> String {
-    match key {
-        Key::Named(named_key) => match os::os_specific_key_name(named_key) {
-            Some(name) => name.to_string(),
-            None => format!("{named_key:?}"),
-        },
-        Key::Character(c) => c.to_ascii_uppercase(),
-        Key::Unidentified(native_key) => match native_key {
-            NativeKey::Unidentified => "<unknown>".to_string(),
-            NativeKey::Android(sc) => format!("SC{sc}"),
-            NativeKey::MacOS(sc) => format!("SC{sc}"),
-            NativeKey::Windows(sc) => format!("SC{sc}"),
-            NativeKey::Xkb(sc) => format!("SC{sc}"),
-            NativeKey::Web(smol_str) => format!("{smol_str}"),
-        },
-        Key::Dead(None) => "<unknown>".to_string(),
-        Key::Dead(Some(c)) => c.into(),
-    }
+    Platform::NATIVE.key_name(key)
+}
+
+#[cfg(feature = "winit")]
+/// Parses a virtual key name produced by [`key_name()`] back into a virtual
+/// key, or returns `None` if it is not a valid key name.
+///
+/// This accepts OS-specific names (such as "Win" on Windows), the `Debug` name
+/// of any [`NamedKey`](winit::keyboard::NamedKey), the `SC{n}` format for keys
+/// with no known name, `"<unknown>"`, and any single character. Letters are
+/// parsed as lowercase, since [`key_name()`] uppercases them. If there is no
+/// exact match, names are matched ignoring case and the spaces between words,
+/// so that "page up" and "pageup" both match "PageUp".
+///
+/// Names used by other platforms (such as "Return" on macOS) are also
+/// accepted, but names that differ in meaning between platforms are
+/// interpreted using the OS's conventions. Use [`Platform::parse_key()`] to
+/// parse names produced for a particular platform.
+pub fn parse_key(s: &str) -> Option<Key> {
+    Platform::NATIVE.parse_key(s)
 }
@@ -0,0 +1,261 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use super::KeymapError;
+
+/// Environment variable that overrides the order in which backends are tried
+/// when querying the keyboard layout automatically.
+pub const BACKEND_ENV_VAR: &str = "KEY_NAMES_BACKEND";
+
+/// Backend used to query the keyboard layout on Linux.
+///
+/// The [`Display`](fmt::Display) implementation gives the lowercase name used
+/// in the `KEY_NAMES_BACKEND` environment variable, such as `wayland`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Wayland, which requires the `wayland` feature.
+    Wayland,
+    /// X11, which requires the `x11` feature.
+    X11,
+    /// Default keymap compiled from RMLVO names (rules, model, layout,
+    /// variant, and options), which may be set using the `XKB_DEFAULT_*`
+    /// environment variables. This does not connect to a display server, so
+    /// it is suitable for TTY applications.
+    Evdev,
+    /// US QWERTY keymap embedded in the crate, which does not depend on
+    /// environment variables or the XKB data files installed on the system.
+    Static,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Wayland => write!(f, "wayland"),
+            Backend::X11 => write!(f, "x11"),
+            Backend::Evdev => write!(f, "evdev"),
+            Backend::Static => write!(f, "static"),
+        }
+    }
+}
+
+impl FromStr for Backend {
+    type Err = KeymapError;
+
+    /// Parses a backend name, ignoring case: `wayland`, `x11`, `evdev`, or
+    /// `static`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Backend::ALL
+            .into_iter()
+            .find(|backend| backend.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| KeymapError::UnknownBackend(s.to_string()))
+    }
+}
+
+impl Backend {
+    /// All backends.
+    pub const ALL: [Backend; 4] = [
+        Backend::Wayland,
+        Backend::X11,
+        Backend::Evdev,
+        Backend::Static,
+    ];
+
+    /// Backends in the order they are tried when querying the keyboard layout
+    /// automatically, unless overridden by the `KEY_NAMES_BACKEND` environment
+    /// variable. Backends that are not enabled are skipped.
+    pub const DEFAULT_ORDER: [Backend; 2] = [Backend::Wayland, Backend::X11];
+
+    /// Returns whether the backend is enabled by its cargo feature. The
+    /// `evdev` and `static` backends are always enabled.
+    pub const fn is_enabled(self) -> bool {
+        match self {
+            Backend::Wayland => cfg!(feature = "wayland"),
+            Backend::X11 => cfg!(feature = "x11"),
+            Backend::Evdev | Backend::Static => true,
+        }
+    }
+
+    /// Parses a comma-separated list of backends, such as `"x11,wayland"`, in
+    /// the format of the `KEY_NAMES_BACKEND` environment variable.
+    pub fn parse_list(s: &str) -> Result<Vec<Backend>, KeymapError> {
+        s.split(',').map(str::parse).collect()
+    }
+}
+
+/// Attempt to query the keyboard layout using a particular backend.
+#[derive(Debug, Clone)]
+pub struct BackendAttempt {
+    /// Backend that was attempted.
+    pub backend: Backend,
+    /// Outcome of the attempt.
+    pub result: Result<(), Arc<KeymapError>>,
+}
+
+/// Record of how the keyboard layout was queried automatically, for
+/// diagnosing why the wrong backend was used or why no backend succeeded.
+///
+/// The [`Display`](fmt::Display) implementation produces a multi-line summary
+/// suitable for bug reports.
+#[derive(Debug, Default, Clone)]
+pub struct BackendDiagnostics {
+    /// Each backend that was attempted, in order, along with its outcome.
+    /// Backends after the first successful one are not attempted.
+    pub attempts: Vec<BackendAttempt>,
+    /// Value of the `WAYLAND_DISPLAY` environment variable.
+    pub wayland_display: Option<String>,
+    /// Value of the `DISPLAY` environment variable.
+    pub display: Option<String>,
+    /// Value of the `XDG_SESSION_TYPE` environment variable.
+    pub xdg_session_type: Option<String>,
+    /// Value of the `KEY_NAMES_BACKEND` environment variable, which is only
+    /// consulted when the backends are chosen automatically.
+    pub key_names_backend: Option<String>,
+}
+
+impl fmt::Display for BackendDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let env_vars = [
+            ("WAYLAND_DISPLAY", &self.wayland_display),
+            ("DISPLAY", &self.display),
+            ("XDG_SESSION_TYPE", &self.xdg_session_type),
+            (BACKEND_ENV_VAR, &self.key_names_backend),
+        ];
+        for (name, value) in env_vars {
+            match value {
+                Some(value) => writeln!(f, "{name}={value:?}")?,
+                None => writeln!(f, "{name} is not set")?,
+            }
+        }
+        if self.attempts.is_empty() {
+            writeln!(f, "no backends attempted")?;
+        }
+        for attempt in &self.attempts {
+            match &attempt.result {
+                Ok(()) => writeln!(f, "{}: ok", attempt.backend)?,
+                Err(e) => writeln!(f, "{}: {e}", attempt.backend)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BackendDiagnostics {
+    /// Constructs diagnostics with no attempts, reading the environment
+    /// variables that affect backend selection.
+    fn from_env() -> Self {
+        BackendDiagnostics {
+            attempts: vec![],
+            wayland_display: std::env::var("WAYLAND_DISPLAY").ok(),
+            display: std::env::var("DISPLAY").ok(),
+            xdg_session_type: std::env::var("XDG_SESSION_TYPE").ok(),
+            key_names_backend: std::env::var(BACKEND_ENV_VAR).ok(),
+        }
+    }
+
+    /// Returns the backend that successfully queried the keyboard layout, or
+    /// `None` if every backend failed.
+    pub fn backend(&self) -> Option<Backend> {
+        self.attempts
+            .iter()
+            .find(|attempt| attempt.result.is_ok())
+            .map(|attempt| attempt.backend)
+    }
+
+    /// Returns the error from a backend, or `None` if it was not attempted or
+    /// succeeded.
+    pub fn error(&self, backend: Backend) -> Option<&Arc<KeymapError>> {
+        self.attempts
+            .iter()
+            .find(|attempt| attempt.backend == backend)
+            .and_then(|attempt| attempt.result.as_ref().err())
+    }
+
+    /// Decides which error to report when no backend succeeded.
+    fn choose_error(&self) -> Arc<KeymapError> {
+        if let (Some(wayland_error), Some(x11_error)) =
+            (self.error(Backend::Wayland), self.error(Backend::X11))
+        {
+            let error = match self.xdg_session_type.as_deref() {
+                Some("wayland") if self.wayland_display.is_some() => wayland_error,
+                _ => x11_error,
+            };
+            return Arc::clone(error);
+        }
+        match self
+            .attempts
+            .iter()
+            .find_map(|attempt| attempt.result.as_ref().err())
+        {
+            Some(e) => Arc::clone(e),
+            None => Arc::new(KeymapError::NoBackend),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_DIAGNOSTICS: RefCell<Option<BackendDiagnostics>> = const { RefCell::new(None) };
+}
+
+/// Returns diagnostics from the most recent time that the keyboard layout was
+/// queried automatically on the current thread, such as by
+/// [`Layout::new()`](crate::Layout::new) or by the free functions in this
+/// crate, or `None` if it hasn't been.
+pub fn last_backend_diagnostics() -> Option<BackendDiagnostics> {
+    LAST_DIAGNOSTICS.with_borrow(|diagnostics| diagnostics.clone())
+}
+
+/// Tries each backend in order until one succeeds, and records the
+/// diagnostics for [`last_backend_diagnostics()`].
+///
+/// If `backends` is `None`, the order is given by the `KEY_NAMES_BACKEND`
+/// environment variable if it is set and not empty, or else [`Backend::DEFAULT_ORDER`]
+/// without any disabled backends.
+pub(super) fn try_backends<T>(
+    backends: Option<&[Backend]>,
+    mut attempt: impl FnMut(Backend) -> Result<T, KeymapError>,
+) -> (Result<T, Arc<KeymapError>>, BackendDiagnostics) {
+    let mut diagnostics = BackendDiagnostics::from_env();
+    let result = match backends {
+        Some(backends) => Ok(backends.to_vec()),
+        None => match diagnostics.key_names_backend.as_deref() {
+            Some(s) if !s.trim().is_empty() => Backend::parse_list(s),
+            _ => Ok(Backend::DEFAULT_ORDER
+                .into_iter()
+                .filter(|backend| backend.is_enabled())
+                .collect()),
+        },
+    };
+    let ret = match result {
+        Ok(backends) => try_each_backend(&backends, &mut attempt, &mut diagnostics)
+            .ok_or_else(|| diagnostics.choose_error()),
+        Err(e) => Err(Arc::new(e)),
+    };
+    LAST_DIAGNOSTICS.set(Some(diagnostics.clone()));
+    (ret, diagnostics)
+}
+
+fn try_each_backend<T>(
+    backends: &[Backend],
+    attempt: &mut impl FnMut(Backend) -> Result<T, KeymapError>,
+    diagnostics: &mut BackendDiagnostics,
+) -> Option<T> {
+    for &backend in backends {
+        match attempt(backend) {
+            Ok(value) => {
+                diagnostics.attempts.push(BackendAttempt {
+                    backend,
+                    result: Ok(()),
+                });
+                return Some(value);
+            }
+            Err(e) => diagnostics.attempts.push(BackendAttempt {
+                backend,
+                result: Err(Arc::new(e)),
+            }),
+        }
+    }
+    None
+}
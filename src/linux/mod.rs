@@ -0,0 +1,712 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use thiserror::Error;
+#[cfg(feature = "wayland")]
+use wayland_client::protocol::wl_keyboard;
+#[cfg(feature = "winit")]
+use winit::keyboard::{NativeKey, NativeKeyCode, PhysicalKey};
+#[cfg(feature = "winit")]
+use winit::platform::scancode::PhysicalKeyExtScancode;
+use xkbcommon::xkb;
+
+use crate::{KeyNamesError, Level};
+
+mod diagnostics;
+#[cfg(any(feature = "x11", feature = "wayland"))]
+mod watcher;
+#[cfg(feature = "wayland")]
+mod wayland;
+#[cfg(feature = "x11")]
+mod x11;
+
+pub use diagnostics::{
+    last_backend_diagnostics, Backend, BackendAttempt, BackendDiagnostics, BACKEND_ENV_VAR,
+};
+#[cfg(any(feature = "x11", feature = "wayland"))]
+pub use watcher::LayoutWatcher;
+#[cfg(feature = "x11")]
+pub use x11::X11KeyboardDevice;
+
+#[cfg(feature = "winit")]
+pub fn native_key_code(sc: u32) -> Option<NativeKeyCode> {
+    Some(NativeKeyCode::Xkb(sc))
+}
+
+#[cfg(feature = "winit")]
+pub fn native_key(sc: u32) -> Option<NativeKey> {
+    Some(NativeKey::Xkb(sc))
+}
+
+#[derive(Clone)]
+pub struct Layout {
+    keymap: xkb::Keymap,
+    /// Active layout group.
+    group: xkb::LayoutIndex,
+}
+
+impl fmt::Debug for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Layout")
+            .field("group", &self.group)
+            .finish_non_exhaustive()
+    }
+}
+
+impl From<xkb::Keymap> for Layout {
+    fn from(keymap: xkb::Keymap) -> Self {
+        Layout { keymap, group: 0 }
+    }
+}
+
+impl Layout {
+    pub fn new() -> Result<Self, KeyNamesError> {
+        new_keymap().0.map_err(KeyNamesError::Keymap)
+    }
+
+    pub fn try_scancode_name(&self, sc: u32) -> Option<String> {
+        self.scancode_name(sc, self.group)
+    }
+
+    #[cfg(feature = "winit")]
+    pub fn try_physical_key_name(&self, physical_key: PhysicalKey) -> Option<String> {
+        self.try_physical_key_name_in_group(physical_key, self.group)
+    }
+
+    #[cfg(feature = "winit")]
+    pub fn try_physical_key_name_in_group(
+        &self,
+        physical_key: PhysicalKey,
+        group: xkb::LayoutIndex,
+    ) -> Option<String> {
+        physical_key
+            .to_scancode()
+            .and_then(|sc| self.scancode_name(sc, group))
+    }
+
+    /// Returns `None` if the key produces no symbol in the group.
+    pub fn scancode_name(&self, sc: u32, group: xkb::LayoutIndex) -> Option<String> {
+        let mut state = xkb::State::new(&self.keymap);
+        // Lock the group so that xkbcommon wraps it if it is out of range.
+        state.update_mask(0, 0, 0, 0, 0, group);
+        let keysym = state.key_get_one_sym(keycode(sc)?);
+        (keysym != xkb::Keysym::NoSymbol).then(|| keysym_name(keysym))
+    }
+
+    pub fn scancode_level_names(&self, sc: u32, group: xkb::LayoutIndex) -> Vec<(Level, String)> {
+        let Some(keycode) = keycode(sc) else {
+            return vec![];
+        };
+
+        let mut seen_levels = vec![];
+        let mut ret: Vec<(Level, String)> = vec![];
+        for (level, state) in self.level_states(group) {
+            let layout = state.key_get_layout(keycode);
+            let xkb_level = state.key_get_level(keycode, layout);
+
+            // Skip levels that aren't distinct for this key.
+            if seen_levels.contains(&xkb_level) {
+                continue;
+            }
+            seen_levels.push(xkb_level);
+
+            let syms = self
+                .keymap
+                .key_get_syms_by_level(keycode, layout, xkb_level);
+            if let Some(&keysym) = syms.first() {
+                let name = keysym_name(keysym);
+                // Letters are uppercased, so they often have the same name on
+                // multiple levels.
+                if ret.iter().all(|(_, existing)| *existing != name) {
+                    ret.push((level, name));
+                }
+            }
+        }
+        ret
+    }
+
+    pub fn scancode_for_char(&self, c: char) -> Option<(u32, Level)> {
+        self.find_scancode_for_char(c, |_| true)
+    }
+
+    #[cfg(feature = "winit")]
+    pub fn physical_key_for_char(&self, c: char) -> Option<(PhysicalKey, Level)> {
+        // Keys that winit doesn't recognize (such as dedicated currency keys)
+        // are only used as a last resort.
+        let is_identified =
+            |sc| !matches!(PhysicalKey::from_scancode(sc), PhysicalKey::Unidentified(_));
+        let (sc, level) = self
+            .find_scancode_for_char(c, is_identified)
+            .or_else(|| self.find_scancode_for_char(c, |_| true))?;
+        Some((PhysicalKey::from_scancode(sc), level))
+    }
+
+    /// Returns the scancode and level that produce a character, considering
+    /// only scancodes for which `filter` returns `true`.
+    fn find_scancode_for_char(
+        &self,
+        c: char,
+        filter: impl Fn(u32) -> bool,
+    ) -> Option<(u32, Level)> {
+        let min_keycode = self.keymap.min_keycode().raw();
+        let max_keycode = self.keymap.max_keycode().raw();
+
+        // Search each level in order so that keys that don't require
+        // modifiers are preferred.
+        for (level, state) in self.level_states(self.group) {
+            for raw_keycode in min_keycode..=max_keycode {
+                let Some(sc) = raw_keycode.checked_sub(8) else {
+                    continue;
+                };
+                if !filter(sc) {
+                    continue;
+                }
+                let keycode = xkb::Keycode::new(raw_keycode);
+                let layout = state.key_get_layout(keycode);
+                let xkb_level = state.key_get_level(keycode, layout);
+                let syms = self
+                    .keymap
+                    .key_get_syms_by_level(keycode, layout, xkb_level);
+                if syms
+                    .iter()
+                    .any(|&sym| xkb::keysym_to_utf32(sym) == c as u32)
+                {
+                    return Some((sc, level));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns an XKB state for each level with the corresponding modifiers
+    /// held and the given group locked.
+    fn level_states(&self, group: xkb::LayoutIndex) -> Vec<(Level, xkb::State)> {
+        let shift_mask = 1 << self.keymap.mod_get_index(xkb::MOD_NAME_SHIFT);
+        // AltGr is conventionally mapped to `ISO_Level3_Shift`.
+        let altgr_mask = 1 << self.keymap.mod_get_index(xkb::MOD_NAME_ISO_LEVEL3_SHIFT);
+
+        Level::ALL
+            .into_iter()
+            .map(|level| {
+                let mut mods = 0;
+                if level.shift() {
+                    mods |= shift_mask;
+                }
+                if level.altgr() {
+                    mods |= altgr_mask;
+                }
+                let mut state = xkb::State::new(&self.keymap);
+                state.update_mask(mods, 0, 0, 0, 0, group);
+                (level, state)
+            })
+            .collect()
+    }
+
+    /// Returns the keys in the keymap that winit doesn't recognize, such as
+    /// dedicated currency keys.
+    #[cfg(feature = "winit")]
+    pub fn unidentified_physical_keys(&self) -> Vec<PhysicalKey> {
+        let min_keycode = self.keymap.min_keycode().raw();
+        let max_keycode = self.keymap.max_keycode().raw();
+        (min_keycode..=max_keycode)
+            .filter_map(|raw_keycode| raw_keycode.checked_sub(8))
+            .map(PhysicalKey::from_scancode)
+            .filter(|physical_key| matches!(physical_key, PhysicalKey::Unidentified(_)))
+            .collect()
+    }
+}
+
+impl crate::Layout {
+    /// Queries the user's current keyboard layout like
+    /// [`Layout::new()`](crate::Layout::new), and also returns which backends
+    /// were attempted and why any of them failed.
+    ///
+    /// See also [`last_backend_diagnostics()`](crate::last_backend_diagnostics).
+    pub fn new_with_diagnostics() -> (Result<Self, KeyNamesError>, BackendDiagnostics) {
+        let (result, diagnostics) = new_keymap();
+        (result.map(Self).map_err(KeyNamesError::Keymap), diagnostics)
+    }
+
+    /// Queries the user's current keyboard layout using the first of
+    /// `backends` that succeeds, ignoring the `KEY_NAMES_BACKEND` environment
+    /// variable.
+    ///
+    /// Backends that are not enabled by their cargo feature fail with
+    /// [`KeymapError::BackendDisabled`]. The attempts are recorded for
+    /// [`last_backend_diagnostics()`](crate::last_backend_diagnostics).
+    pub fn from_backends(backends: &[Backend]) -> Result<Self, KeyNamesError> {
+        let (result, _) = diagnostics::try_backends(Some(backends), backend_keymap);
+        result.map(Self).map_err(KeyNamesError::Keymap)
+    }
+
+    /// Queries the user's current keyboard layout using a particular backend,
+    /// ignoring the `KEY_NAMES_BACKEND` environment variable.
+    pub fn from_backend(backend: Backend) -> Result<Self, KeyNamesError> {
+        Self::from_backends(&[backend])
+    }
+
+    /// Constructs a layout by compiling an XKB keymap from RMLVO names (rules,
+    /// model, layout, variant, and options), without connecting to X11 or
+    /// Wayland.
+    ///
+    /// Empty strings use the system defaults, which may be overridden by the
+    /// `XKB_DEFAULT_*` environment variables. Multiple layouts may be specified
+    /// as a comma-separated list, such as `layout = "us,de"` and `variant =
+    /// "dvorak,"`.
+    pub fn from_xkb_names(
+        rules: &str,
+        model: &str,
+        layout: &str,
+        variant: &str,
+        options: Option<&str>,
+    ) -> Result<Self, KeyNamesError> {
+        Ok(Self::from_xkb_keymap(new_keymap_from_names(
+            rules, model, layout, variant, options,
+        )?))
+    }
+
+    /// Constructs a layout by compiling an XKB keymap for a layout name such as
+    /// `"de"`, `"fr(azerty)"`, or `"us(dvorak)"`, without connecting to X11 or
+    /// Wayland. The default rules, model, and options are used.
+    pub fn from_xkb_layout_name(name: &str) -> Result<Self, KeyNamesError> {
+        let (layout, variant) = match name.trim().split_once('(') {
+            Some((layout, variant)) => (layout, variant.trim_end_matches(')')),
+            None => (name.trim(), ""),
+        };
+        Self::from_xkb_names("", "", layout, variant, None)
+    }
+
+    /// Constructs a layout by compiling an XKB keymap from a string in the
+    /// text format produced by `xkbcomp` and sent by Wayland compositors
+    /// (`XKB_KEYMAP_FORMAT_TEXT_V1`).
+    pub fn from_xkb_keymap_string(keymap: &str) -> Result<Self, KeyNamesError> {
+        Ok(Self::from_xkb_keymap(new_keymap_from_string(keymap)?))
+    }
+
+    /// Constructs a layout by compiling an XKB keymap from a file in the text
+    /// format produced by `xkbcomp` and sent by Wayland compositors
+    /// (`XKB_KEYMAP_FORMAT_TEXT_V1`).
+    pub fn from_xkb_keymap_file(path: impl AsRef<Path>) -> Result<Self, KeyNamesError> {
+        let keymap = std::fs::read_to_string(path).map_err(KeymapError::Io)?;
+        Self::from_xkb_keymap_string(&keymap)
+    }
+
+    /// Constructs a layout from an existing XKB keymap, with group 0 active.
+    pub fn from_xkb_keymap(keymap: xkb::Keymap) -> Self {
+        Self(Layout::from(keymap))
+    }
+
+    /// Returns the XKB keymap used by the layout.
+    pub fn xkb_keymap(&self) -> &xkb::Keymap {
+        &self.0.keymap
+    }
+
+    /// Returns the index of the active layout group (also called the
+    /// effective layout index), which is used by
+    /// [`Layout::physical_key_name()`](crate::Layout::physical_key_name).
+    pub fn active_group(&self) -> xkb::LayoutIndex {
+        self.0.group
+    }
+
+    /// Sets the index of the active layout group, such as from the `group`
+    /// field of `wl_keyboard::Event::Modifiers`.
+    ///
+    /// Out-of-range indices are wrapped according to the keymap.
+    pub fn set_active_group(&mut self, group: xkb::LayoutIndex) {
+        self.0.group = group;
+    }
+
+    /// Returns the names of the groups in the keymap, such as `"English
+    /// (US)"` and `"Russian"`.
+    pub fn group_names(&self) -> Vec<String> {
+        self.0.keymap.layouts().map(str::to_string).collect()
+    }
+
+    /// Returns a human-friendly name for a physical key in a specific layout
+    /// group, regardless of which group is active.
+    #[cfg(feature = "winit")]
+    pub fn physical_key_name_in_group(
+        &self,
+        physical_key: PhysicalKey,
+        group: xkb::LayoutIndex,
+    ) -> String {
+        self.0
+            .try_physical_key_name_in_group(physical_key, group)
+            .unwrap_or_else(|| crate::unknown_physical_key_name(physical_key))
+    }
+
+    /// Returns a human-friendly name for each distinct symbol produced by the
+    /// key with an evdev scancode in the active layout group, along with the
+    /// shift level that produces it.
+    ///
+    /// This does not require the `winit` feature. See
+    /// [`Layout::physical_key_level_names()`](crate::Layout::physical_key_level_names).
+    pub fn scancode_level_names(&self, scancode: u32) -> Vec<(Level, String)> {
+        self.0.scancode_level_names(scancode, self.0.group)
+    }
+
+    /// Returns a human-friendly name for each distinct symbol produced by a
+    /// physical key in the active layout group, along with the shift level
+    /// that produces it. For example, `Digit2` on a German layout produces
+    /// "2", "\"", and "²" at the base, <key>Shift</key>, and <key>AltGr</key>
+    /// levels respectively.
+    ///
+    /// Levels that produce no symbol or the same name as a previous level are
+    /// omitted.
+    #[cfg(feature = "winit")]
+    pub fn physical_key_level_names(&self, physical_key: PhysicalKey) -> Vec<(Level, String)> {
+        match physical_key.to_scancode() {
+            Some(sc) => self.0.scancode_level_names(sc, self.0.group),
+            None => vec![],
+        }
+    }
+
+    /// Returns a human-friendly name for a physical key in each layout group
+    /// of the keymap, indexed by group.
+    #[cfg(feature = "winit")]
+    pub fn physical_key_names_in_all_groups(&self, physical_key: PhysicalKey) -> Vec<String> {
+        (0..self.0.keymap.num_layouts())
+            .map(|group| self.physical_key_name_in_group(physical_key, group))
+            .collect()
+    }
+
+    /// Returns a human-friendly name for a physical key containing its name in
+    /// each layout group, joined by `separator`, similar to a keycap sticker.
+    /// For example, `KeyQ` with the layouts "us,ru" and the separator `" / "`
+    /// produces "Q / Й". Duplicate names are omitted.
+    #[cfg(feature = "winit")]
+    pub fn physical_key_name_all_groups(
+        &self,
+        physical_key: PhysicalKey,
+        separator: &str,
+    ) -> String {
+        let mut names = self.physical_key_names_in_all_groups(physical_key);
+        let mut seen = std::collections::HashSet::new();
+        names.retain(|name| seen.insert(name.clone()));
+        names.join(separator)
+    }
+}
+
+/// Converts an evdev scancode to an XKB keycode, or returns `None` if it is out
+/// of range.
+///
+/// According to the xkbcommon documentation, there is a fixed offset of 8
+/// between X11-compatible keymaps and Linux evdev scancodes:
+/// https://docs.rs/xkbcommon/0.8.0/xkbcommon/xkb/struct.Keycode.html
+fn keycode(sc: u32) -> Option<xkb::Keycode> {
+    sc.checked_add(8).map(xkb::Keycode::new)
+}
+
+fn keysym_name(keysym: xkb::Keysym) -> String {
+    if let Some(name) = special_keysym_name(keysym) {
+        return name.to_string();
+    }
+
+    // Prefer the character produced by the key, if it's printable.
+    if let Some(c) = char::from_u32(xkb::keysym_to_utf32(keysym)) {
+        if !c.is_control() && !c.is_whitespace() {
+            let mut upper = c.to_uppercase();
+            return match (upper.next(), upper.next()) {
+                (Some(upper), None) => upper.to_string(),
+                _ => c.to_string(), // such as `ß`
+            };
+        }
+    }
+
+    let mut key_name = xkb::keysym_get_name(keysym);
+    if key_name.len() == 1 {
+        key_name.make_ascii_uppercase();
+    }
+    match key_name.strip_prefix("XF86") {
+        Some(rest) => rest.to_string(),
+        None => key_name,
+    }
+}
+
+/// Returns a name for a keysym that doesn't produce a printable character, or
+/// whose character isn't a good name.
+fn special_keysym_name(keysym: xkb::Keysym) -> Option<&'static str> {
+    Some(match keysym.raw() {
+        // Better names for numpad keys
+        xkb::keysyms::KEY_KP_Insert => "Numpad0",
+        xkb::keysyms::KEY_KP_End => "Numpad1",
+        xkb::keysyms::KEY_KP_Down => "Numpad2",
+        xkb::keysyms::KEY_KP_Next => "Numpad3",
+        xkb::keysyms::KEY_KP_Left => "Numpad4",
+        xkb::keysyms::KEY_KP_Begin => "Numpad5",
+        xkb::keysyms::KEY_KP_Right => "Numpad6",
+        xkb::keysyms::KEY_KP_Home => "Numpad7",
+        xkb::keysyms::KEY_KP_Up => "Numpad8",
+        xkb::keysyms::KEY_KP_Prior => "Numpad9",
+        xkb::keysyms::KEY_KP_Add => "NumpadAdd",
+        xkb::keysyms::KEY_KP_Decimal => "NumpadComma",
+        xkb::keysyms::KEY_KP_Delete => "NumpadDecimal",
+        xkb::keysyms::KEY_KP_Divide => "NumpadDivide",
+        xkb::keysyms::KEY_KP_Enter => "NumpadEnter",
+        xkb::keysyms::KEY_KP_Equal => "NumpadEqual",
+        xkb::keysyms::KEY_KP_Multiply => "NumpadMultiply",
+        xkb::keysyms::KEY_KP_Subtract => "NumpadSubtract",
+
+        // Modifiers, named consistently with the hard-coded tables for other
+        // platforms
+        xkb::keysyms::KEY_Alt_L => "Left Alt",
+        xkb::keysyms::KEY_Alt_R => "Right Alt",
+        xkb::keysyms::KEY_Control_L => "Left Control",
+        xkb::keysyms::KEY_Control_R => "Right Control",
+        xkb::keysyms::KEY_Hyper_L => "Left Hyper",
+        xkb::keysyms::KEY_Hyper_R => "Right Hyper",
+        xkb::keysyms::KEY_Meta_L => "Left Meta",
+        xkb::keysyms::KEY_Meta_R => "Right Meta",
+        xkb::keysyms::KEY_Shift_L => "Left Shift",
+        xkb::keysyms::KEY_Shift_R => "Right Shift",
+        xkb::keysyms::KEY_Super_L => "Left Super",
+        xkb::keysyms::KEY_Super_R => "Right Super",
+        xkb::keysyms::KEY_ISO_Level3_Shift => "AltGr",
+        xkb::keysyms::KEY_Caps_Lock => "Caps Lock",
+        xkb::keysyms::KEY_Num_Lock => "Num Lock",
+        xkb::keysyms::KEY_Scroll_Lock => "Scroll Lock",
+
+        // Editing and navigation keys
+        xkb::keysyms::KEY_BackSpace => "Backspace",
+        xkb::keysyms::KEY_Delete => "Delete",
+        xkb::keysyms::KEY_Escape => "Esc",
+        xkb::keysyms::KEY_Insert => "Insert",
+        xkb::keysyms::KEY_Return => "Enter",
+        xkb::keysyms::KEY_space => "Space",
+        xkb::keysyms::KEY_Tab | xkb::keysyms::KEY_ISO_Left_Tab => "Tab",
+        xkb::keysyms::KEY_Down => "Down",
+        xkb::keysyms::KEY_End => "End",
+        xkb::keysyms::KEY_Home => "Home",
+        xkb::keysyms::KEY_Left => "Left",
+        xkb::keysyms::KEY_Next => "Page Down",
+        xkb::keysyms::KEY_Prior => "Page Up",
+        xkb::keysyms::KEY_Right => "Right",
+        xkb::keysyms::KEY_Up => "Up",
+
+        // Miscellaneous keys
+        xkb::keysyms::KEY_Break => "Break",
+        xkb::keysyms::KEY_Menu => "Menu",
+        xkb::keysyms::KEY_Multi_key => "Compose",
+        xkb::keysyms::KEY_Pause => "Pause",
+        xkb::keysyms::KEY_Print => "Print Screen",
+
+        // Dead keys, named using the equivalent spacing character
+        xkb::keysyms::KEY_dead_abovedot => "˙",
+        xkb::keysyms::KEY_dead_abovering => "°",
+        xkb::keysyms::KEY_dead_acute => "´",
+        xkb::keysyms::KEY_dead_breve => "˘",
+        xkb::keysyms::KEY_dead_caron => "ˇ",
+        xkb::keysyms::KEY_dead_cedilla => "¸",
+        xkb::keysyms::KEY_dead_circumflex => "^",
+        xkb::keysyms::KEY_dead_diaeresis => "¨",
+        xkb::keysyms::KEY_dead_doubleacute => "˝",
+        xkb::keysyms::KEY_dead_grave => "`",
+        xkb::keysyms::KEY_dead_macron => "¯",
+        xkb::keysyms::KEY_dead_ogonek => "˛",
+        xkb::keysyms::KEY_dead_tilde => "~",
+
+        _ => return None,
+    })
+}
+
+/// Constructs a keymap using the first backend that succeeds, in the order
+/// given by the `KEY_NAMES_BACKEND` environment variable or else the default
+/// order.
+fn new_keymap() -> (Result<Layout, Arc<KeymapError>>, BackendDiagnostics) {
+    diagnostics::try_backends(None, backend_keymap)
+}
+
+/// Constructs a keymap using a particular backend.
+fn backend_keymap(backend: Backend) -> Result<Layout, KeymapError> {
+    match backend {
+        #[cfg(feature = "wayland")]
+        Backend::Wayland => wayland::new_wayland_keymap(),
+        #[cfg(feature = "x11")]
+        Backend::X11 => x11::new_x11_keymap(),
+        Backend::Evdev => new_keymap_from_names("", "", "", "", None).map(Layout::from),
+        Backend::Static => new_static_keymap().map(Layout::from),
+        #[allow(unreachable_patterns)]
+        _ => Err(KeymapError::BackendDisabled(backend)),
+    }
+}
+
+/// Error encountered while getting the keymap from X11 or Wayland.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum KeymapError {
+    /// I/O error while reading the keymap.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// No backend was attempted, because neither the `x11` nor the `wayland`
+    /// feature is enabled or because an empty list of backends was given.
+    #[error("no backend available (enable the `x11` or `wayland` feature)")]
+    NoBackend,
+    /// The backend is not enabled by its cargo feature.
+    #[error("{0} backend is not enabled")]
+    BackendDisabled(Backend),
+    /// The `KEY_NAMES_BACKEND` environment variable contains an unknown
+    /// backend name.
+    #[error("unknown backend {0:?} (expected `wayland`, `x11`, `evdev`, or `static`)")]
+    UnknownBackend(String),
+
+    /// Unable to connect to the X server.
+    #[error("unable to connect to X server")]
+    X11Connect,
+    /// The X server does not support the XInput 2 extension, which is used to
+    /// list keyboard devices.
+    #[error("X server does not support XInput 2")]
+    MissingXInput,
+
+    /// Error while dispatching Wayland events.
+    #[cfg(feature = "wayland")]
+    #[error("wayland dispatch error")]
+    Wayland(#[from] wayland_client::DispatchError),
+    /// Unable to connect to the Wayland compositor.
+    #[error("unable to connect to wayland")]
+    WaylandConnect,
+    /// The Wayland compositor does not advertise a `wl_seat`.
+    #[error("wl_seat not found in available interfaces")]
+    MissingWlSeat,
+    /// No `wl_seat` has the requested name.
+    #[error("no wl_seat named {0:?}")]
+    UnknownWlSeat(String),
+    /// The `wl_seat` does not have a keyboard.
+    #[error("wl_seat does not have keyboard capability")]
+    MissingKeyboardCapability,
+    /// xkbcommon failed to compile the keymap.
+    #[error("failed to create keymap")]
+    FailedToCreateKeymap,
+    /// The Wayland compositor sent a keymap in a format other than XKB v1.
+    #[cfg(feature = "wayland")]
+    #[error("unsupported keymap format: {0:?}")]
+    UnsupportedKeymapFormat(wayland_client::WEnum<wl_keyboard::KeymapFormat>),
+}
+
+impl From<KeymapError> for KeyNamesError {
+    fn from(e: KeymapError) -> Self {
+        KeyNamesError::Keymap(Arc::new(e))
+    }
+}
+
+/// Constructs a keymap from RMLVO names.
+fn new_keymap_from_names(
+    rules: &str,
+    model: &str,
+    layout: &str,
+    variant: &str,
+    options: Option<&str>,
+) -> Result<xkb::Keymap, KeymapError> {
+    let ctx = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+    xkb::Keymap::new_from_names(
+        &ctx,
+        rules,
+        model,
+        layout,
+        variant,
+        options.map(str::to_string),
+        xkb::KEYMAP_COMPILE_NO_FLAGS,
+    )
+    .ok_or(KeymapError::FailedToCreateKeymap)
+}
+
+/// Constructs a US QWERTY keymap from a copy embedded in the crate, so that it
+/// does not depend on the XKB data files or the `XKB_DEFAULT_*` environment
+/// variables.
+fn new_static_keymap() -> Result<xkb::Keymap, KeymapError> {
+    // Compiled from `evdev`, `pc105`, and `us` using `xkb_keymap_get_as_string()`.
+    new_keymap_from_string(include_str!("us.xkb"))
+}
+
+/// Constructs a keymap from a string in the XKB text format.
+fn new_keymap_from_string(keymap: &str) -> Result<xkb::Keymap, KeymapError> {
+    let ctx = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+    xkb::Keymap::new_from_string(
+        &ctx,
+        keymap.to_string(),
+        xkb::KEYMAP_FORMAT_TEXT_V1,
+        xkb::KEYMAP_COMPILE_NO_FLAGS,
+    )
+    .ok_or(KeymapError::FailedToCreateKeymap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level_names(names: &[(Level, &str)]) -> Vec<(Level, String)> {
+        names
+            .iter()
+            .map(|&(level, name)| (level, name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_scancode_level_names() {
+        let layout = crate::Layout::from_xkb_layout_name("de").unwrap();
+        // `Digit2`
+        assert_eq!(
+            layout.scancode_level_names(3),
+            level_names(&[
+                (Level::Base, "2"),
+                (Level::Shift, "\""),
+                (Level::AltGr, "²"),
+                (Level::ShiftAltGr, "⅛"),
+            ]),
+        );
+        // `KeyA`, whose letter is uppercased so the shift level is omitted
+        assert_eq!(
+            layout.scancode_level_names(30),
+            level_names(&[(Level::Base, "A"), (Level::AltGr, "Æ")]),
+        );
+        assert_eq!(layout.scancode_level_names(u32::MAX), vec![]);
+    }
+
+    #[test]
+    fn test_keysym_name() {
+        for (keysym, expected) in [
+            (xkb::keysyms::KEY_Prior, "Page Up"),
+            (xkb::keysyms::KEY_Return, "Enter"),
+            (xkb::keysyms::KEY_KP_Prior, "Numpad9"),
+            (xkb::keysyms::KEY_bracketleft, "["),
+            (xkb::keysyms::KEY_apostrophe, "'"),
+            (xkb::keysyms::KEY_a, "A"),
+            (xkb::keysyms::KEY_odiaeresis, "Ö"),
+            (xkb::keysyms::KEY_ssharp, "ß"),
+            (xkb::keysyms::KEY_dead_acute, "´"),
+            (xkb::keysyms::KEY_XF86AudioMute, "AudioMute"),
+            (xkb::keysyms::KEY_XF86Calculator, "Calculator"),
+        ] {
+            assert_eq!(keysym_name(xkb::Keysym::new(keysym)), expected);
+        }
+    }
+
+    #[test]
+    fn test_scancode_for_char() {
+        let layout = crate::Layout::from_xkb_layout_name("de").unwrap();
+        // `KeyY`, `KeyZ`, and `KeyQ`
+        assert_eq!(layout.scancode_for_char('z'), Some((21, Level::Base)));
+        assert_eq!(layout.scancode_for_char('Z'), Some((21, Level::Shift)));
+        assert_eq!(layout.scancode_for_char('y'), Some((44, Level::Base)));
+        assert_eq!(layout.scancode_for_char('@'), Some((16, Level::AltGr)));
+        assert_eq!(layout.scancode_for_char('☃'), None);
+    }
+
+    #[test]
+    #[cfg(feature = "winit")]
+    fn test_physical_key_for_char() {
+        use winit::keyboard::KeyCode;
+
+        let layout = crate::Layout::from_xkb_layout_name("de").unwrap();
+        for (c, key_code, level) in [
+            ('z', KeyCode::KeyY, Level::Base),
+            ('Z', KeyCode::KeyY, Level::Shift),
+            ('@', KeyCode::KeyQ, Level::AltGr),
+            ('ö', KeyCode::Semicolon, Level::Base),
+        ] {
+            let expected = (PhysicalKey::Code(key_code), level);
+            assert_eq!(layout.physical_key_for_char(c), Some(expected), "{c:?}");
+        }
+    }
+}
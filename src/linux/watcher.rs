@@ -0,0 +1,381 @@
+use std::fmt;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex, Weak};
+
+#[cfg(feature = "x11")]
+use xcb::xkb as xcb_xkb;
+use xkbcommon::xkb;
+
+use super::diagnostics::try_backends;
+#[cfg(feature = "wayland")]
+use super::wayland::{wayland_keymap, State};
+#[cfg(feature = "x11")]
+use super::x11::x11_keymap;
+use super::{backend_keymap, Backend, KeymapError, Layout as OsLayout};
+use crate::{KeyNamesError, Layout};
+
+type Callback = Box<dyn FnMut(u64) + Send>;
+
+/// Watches for changes to the user's keyboard layout on X11 or Wayland.
+///
+/// The watcher runs on a background thread with its own connection to the
+/// display server. Each time the keymap changes (for example, when the user
+/// adds a layout or plugs in a different keyboard), the generation counter is
+/// incremented and the callback, if any, is called.
+///
+/// On X11, switching the active group (for example, from "us" to "ru") is also
+/// reported. On Wayland, only keymap changes are reported, because the
+/// compositor only sends the active group to a client with keyboard focus,
+/// which the watcher's own connection never has. Use
+/// [`Layout::set_active_group()`](crate::Layout::set_active_group) with the
+/// group from the application's own `wl_keyboard.modifiers` events instead.
+///
+/// Because XKB keymaps cannot be sent between threads, call
+/// [`LayoutWatcher::layout()`] to construct an up-to-date [`Layout`] on the
+/// current thread, and [`crate::set_default_layout()`] to use it for the free
+/// functions in this crate.
+///
+/// The background thread and its connection are closed when the watcher is
+/// dropped. The `evdev` and `static` backends do not connect to a display
+/// server, so watchers using them never change.
+pub struct LayoutWatcher {
+    shared: Arc<Shared>,
+    /// Write end of a pipe that the background thread polls alongside its
+    /// connection. Dropping it wakes the thread so that it exits.
+    _wakeup: Option<OwnedFd>,
+}
+
+impl fmt::Debug for LayoutWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayoutWatcher")
+            .field("generation", &self.generation())
+            .finish_non_exhaustive()
+    }
+}
+
+impl LayoutWatcher {
+    /// Starts watching the keyboard layout using either Wayland or X11
+    /// automatically, depending on which backends are enabled and the
+    /// `KEY_NAMES_BACKEND` environment variable.
+    pub fn new() -> Result<Self, KeyNamesError> {
+        let (result, _) = try_backends(None, Self::spawn_backend);
+        result.map_err(KeyNamesError::Keymap)
+    }
+
+    /// Starts watching the keyboard layout using the first of `backends` that
+    /// succeeds, ignoring the `KEY_NAMES_BACKEND` environment variable.
+    pub fn from_backends(backends: &[Backend]) -> Result<Self, KeyNamesError> {
+        let (result, _) = try_backends(Some(backends), Self::spawn_backend);
+        result.map_err(KeyNamesError::Keymap)
+    }
+
+    /// Starts watching the keyboard layout using X11.
+    #[cfg(feature = "x11")]
+    pub fn new_x11() -> Result<Self, KeyNamesError> {
+        Ok(Self::spawn(watch_x11)?)
+    }
+
+    /// Starts watching the keyboard layout using Wayland.
+    ///
+    /// Only keymap changes are reported, not changes to the active group.
+    #[cfg(feature = "wayland")]
+    pub fn new_wayland() -> Result<Self, KeyNamesError> {
+        Ok(Self::spawn(watch_wayland)?)
+    }
+
+    /// Sets a callback to be called on the background thread each time the
+    /// keyboard layout changes. The callback receives the new generation
+    /// number.
+    ///
+    /// The callback may call this method to replace itself.
+    pub fn set_callback(&self, callback: impl FnMut(u64) + Send + 'static) {
+        *self.shared.callback.lock().unwrap() = Some(Box::new(callback));
+    }
+
+    /// Returns the number of times the keyboard layout or active group has
+    /// changed since the watcher was started.
+    ///
+    /// This can be polled and compared against a previous value to detect
+    /// changes.
+    pub fn generation(&self) -> u64 {
+        self.shared.generation.load(Ordering::SeqCst)
+    }
+
+    /// Constructs a layout from the most recent keymap and active group.
+    pub fn layout(&self) -> Result<Layout, KeyNamesError> {
+        let mut layout = Layout::from_xkb_keymap_string(&self.shared.keymap.lock().unwrap())?;
+        layout.set_active_group(self.shared.group.load(Ordering::SeqCst));
+        Ok(layout)
+    }
+
+    fn spawn_backend(backend: Backend) -> Result<Self, KeymapError> {
+        match backend {
+            #[cfg(feature = "wayland")]
+            Backend::Wayland => Self::spawn(watch_wayland),
+            #[cfg(feature = "x11")]
+            Backend::X11 => Self::spawn(watch_x11),
+            Backend::Evdev | Backend::Static => {
+                let shared = Arc::new(Shared::default());
+                shared.set_layout(&backend_keymap(backend)?);
+                Ok(LayoutWatcher {
+                    shared,
+                    _wakeup: None,
+                })
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(KeymapError::BackendDisabled(backend)),
+        }
+    }
+
+    fn spawn(
+        watch: fn(Weak<Shared>, OwnedFd, mpsc::Sender<Result<(), KeymapError>>),
+    ) -> Result<Self, KeymapError> {
+        let shared = Arc::new(Shared::default());
+        let weak = Arc::downgrade(&shared);
+        let (wakeup_rx, wakeup_tx) = pipe()?;
+        let (ready_tx, ready_rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("key-names layout watcher".to_string())
+            .spawn(move || watch(weak, wakeup_rx, ready_tx))?;
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(LayoutWatcher {
+                shared,
+                _wakeup: Some(wakeup_tx),
+            }),
+            Ok(Err(e)) => Err(e),
+            Err(mpsc::RecvError) => Err(KeymapError::FailedToCreateKeymap),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    generation: AtomicU64,
+    keymap: Mutex<String>,
+    group: AtomicU32,
+    callback: Mutex<Option<Callback>>,
+}
+
+impl Shared {
+    fn set_keymap(&self, keymap: &xkb::Keymap) {
+        *self.keymap.lock().unwrap() = keymap.get_as_string(xkb::KEYMAP_FORMAT_TEXT_V1);
+    }
+
+    fn set_layout(&self, layout: &OsLayout) {
+        self.set_keymap(&layout.keymap);
+        self.group.store(layout.group, Ordering::SeqCst);
+    }
+
+    fn publish(&self) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        // Release the lock while calling the callback so that it can call
+        // `set_callback()`.
+        let callback = self.callback.lock().unwrap().take();
+        if let Some(mut callback) = callback {
+            callback(generation);
+            self.callback.lock().unwrap().get_or_insert(callback);
+        }
+    }
+}
+
+/// Creates a pipe, returning the read and write ends.
+fn pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    // SAFETY: `fds` has room for the two file descriptors.
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `pipe2()` returned two new file descriptors that are not owned
+    // by anything else.
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+/// Waits until `fd` is readable. Returns `false` if the write end of the
+/// `wakeup` pipe is closed first, or if polling fails.
+fn wait_readable(fd: RawFd, wakeup: &OwnedFd) -> bool {
+    let mut fds = [
+        libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        },
+        libc::pollfd {
+            fd: wakeup.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        },
+    ];
+    loop {
+        // SAFETY: `fds` is a valid array of two `pollfd` structs.
+        if unsafe { libc::poll(fds.as_mut_ptr(), 2, -1) } < 0 {
+            if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return false;
+        }
+        if fds[1].revents != 0 {
+            return false;
+        }
+        if fds[0].revents != 0 {
+            return true;
+        }
+    }
+}
+
+/// Watches for `wl_keyboard` keymap events, which the compositor re-sends
+/// whenever the keymap changes, and modifiers events, which contain the active
+/// group.
+#[cfg(feature = "wayland")]
+fn watch_wayland(
+    shared: Weak<Shared>,
+    wakeup: OwnedFd,
+    ready: mpsc::Sender<Result<(), KeymapError>>,
+) {
+    let result = wayland_client::Connection::connect_to_env()
+        .map_err(|_| KeymapError::WaylandConnect)
+        .and_then(|connection| wayland_keymap(&connection));
+    let (mut event_queue, mut state, seat, layout) = match result {
+        Ok(ok) => ok,
+        Err(e) => {
+            let _ = ready.send(Err(e));
+            return;
+        }
+    };
+    let Some(strong) = shared.upgrade() else {
+        return;
+    };
+    strong.set_layout(&layout);
+    drop(strong);
+    let _ = ready.send(Ok(()));
+
+    let mut group = layout.group;
+    while read_wayland_events(&event_queue, &wakeup)
+        && event_queue.dispatch_pending(&mut state).is_ok()
+    {
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+        let mut changed = false;
+        let seat_state = &mut state.seats[seat];
+        if let Some(keymap) = seat_state.keymap.take() {
+            shared.set_keymap(&keymap);
+            changed = true;
+        }
+        if seat_state.group != group {
+            group = seat_state.group;
+            shared.group.store(group, Ordering::SeqCst);
+            changed = true;
+        }
+        if changed {
+            shared.publish();
+        }
+    }
+}
+
+/// Waits for events from the compositor and reads them into the event queue.
+/// Returns `false` if the watcher was dropped or the connection failed.
+#[cfg(feature = "wayland")]
+fn read_wayland_events(event_queue: &wayland_client::EventQueue<State>, wakeup: &OwnedFd) -> bool {
+    if event_queue.flush().is_err() {
+        return false;
+    }
+    // If there are already events in the queue, don't block.
+    let Some(guard) = event_queue.prepare_read() else {
+        return true;
+    };
+    if !wait_readable(guard.connection_fd().as_raw_fd(), wakeup) {
+        return false;
+    }
+    match guard.read() {
+        Ok(_) => true,
+        Err(wayland_client::backend::WaylandError::Io(e)) => e.kind() == io::ErrorKind::WouldBlock,
+        Err(_) => false,
+    }
+}
+
+/// Watches for XKB `NewKeyboardNotify` and `MapNotify` events, which indicate
+/// that the keymap has changed, and `StateNotify` events, which contain the
+/// active group.
+#[cfg(feature = "x11")]
+fn watch_x11(shared: Weak<Shared>, wakeup: OwnedFd, ready: mpsc::Sender<Result<(), KeymapError>>) {
+    // The XKB extension must be requested up front so that `xcb` can parse XKB
+    // events.
+    let connection =
+        match xcb::Connection::connect_with_extensions(None, &[xcb::Extension::Xkb], &[]) {
+            Ok((connection, _)) => connection,
+            Err(_) => {
+                let _ = ready.send(Err(KeymapError::X11Connect));
+                return;
+            }
+        };
+    let layout = match x11_keymap(&connection) {
+        Ok(layout) => layout,
+        Err(e) => {
+            let _ = ready.send(Err(e));
+            return;
+        }
+    };
+
+    let keymap_events = xcb_xkb::EventType::NEW_KEYBOARD_NOTIFY | xcb_xkb::EventType::MAP_NOTIFY;
+    let map_parts = xcb_xkb::MapPart::all();
+    // Only select state events for group changes, not modifier changes.
+    let group_state = xcb_xkb::StatePart::GROUP_STATE;
+    let select_result = connection.send_and_check_request(&xcb_xkb::SelectEvents {
+        device_spec: xcb_xkb::Id::UseCoreKbd as xcb_xkb::DeviceSpec,
+        affect_which: keymap_events | xcb_xkb::EventType::STATE_NOTIFY,
+        clear: xcb_xkb::EventType::empty(),
+        select_all: keymap_events,
+        affect_map: map_parts,
+        map: map_parts,
+        details: &[xcb_xkb::SelectEventsDetails::StateNotify {
+            affect_state: group_state,
+            state_details: group_state,
+        }],
+    });
+    if select_result.is_err() {
+        let _ = ready.send(Err(KeymapError::X11Connect));
+        return;
+    }
+
+    let Some(strong) = shared.upgrade() else {
+        return;
+    };
+    strong.set_layout(&layout);
+    drop(strong);
+    let _ = ready.send(Ok(()));
+
+    loop {
+        let event = match connection.poll_for_event() {
+            Ok(Some(event)) => event,
+            Ok(None) => {
+                if connection.flush().is_err() || !wait_readable(connection.as_raw_fd(), &wakeup) {
+                    return;
+                }
+                continue;
+            }
+            Err(_) => return,
+        };
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+        match event {
+            xcb::Event::Xkb(
+                xcb_xkb::Event::NewKeyboardNotify(_) | xcb_xkb::Event::MapNotify(_),
+            ) => {
+                if let Ok(layout) = x11_keymap(&connection) {
+                    shared.set_layout(&layout);
+                    shared.publish();
+                }
+            }
+            xcb::Event::Xkb(xcb_xkb::Event::StateNotify(ev)) => {
+                let group = ev.group() as xkb::LayoutIndex;
+                if shared.group.swap(group, Ordering::SeqCst) != group {
+                    shared.publish();
+                }
+            }
+            _ => (),
+        }
+    }
+}
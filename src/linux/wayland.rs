@@ -0,0 +1,391 @@
+use std::os::fd::OwnedFd;
+
+use wayland_client::protocol::{wl_keyboard, wl_registry, wl_seat};
+use wayland_client::Proxy;
+use xkbcommon::xkb;
+
+use super::{KeymapError, Layout};
+use crate::KeyNamesError;
+
+impl crate::Layout {
+    /// Constructs a layout by querying the keymap from Wayland.
+    ///
+    /// Wayland compositors only report the active group to clients with
+    /// keyboard focus, so the active group is usually 0. Use
+    /// [`Layout::set_active_group()`](crate::Layout::set_active_group) to
+    /// update it from the `group` field of `wl_keyboard::Event::Modifiers`.
+    pub fn from_wayland() -> Result<Self, KeyNamesError> {
+        Ok(Self(new_wayland_keymap()?))
+    }
+
+    /// Constructs a layout by querying the keymap from Wayland using an
+    /// existing connection, such as the one used by the application's
+    /// windowing library, instead of opening a new one.
+    ///
+    /// This binds the compositor's seats on a separate event queue, so it does
+    /// not interfere with the application's own event handling, and releases
+    /// them afterwards. The first seat with a keyboard is used. See
+    /// [`Layout::from_wayland()`](crate::Layout::from_wayland) for the active
+    /// group.
+    pub fn from_wayland_connection(
+        connection: &wayland_client::Connection,
+    ) -> Result<Self, KeyNamesError> {
+        let (_event_queue, _state, _seat, keymap) = wayland_keymap(connection)?;
+        Ok(Self(keymap))
+    }
+
+    /// Constructs a layout by querying the keymap of an existing `wl_seat`
+    /// bound by the application.
+    ///
+    /// The seat must have the keyboard capability. This requests a separate
+    /// `wl_keyboard` on its own event queue, so it does not interfere with the
+    /// application's own event handling, and releases it afterwards. See
+    /// [`Layout::from_wayland()`](crate::Layout::from_wayland) for the active
+    /// group.
+    pub fn from_wayland_seat(
+        connection: &wayland_client::Connection,
+        seat: &wl_seat::WlSeat,
+    ) -> Result<Self, KeyNamesError> {
+        let mut state = State::default();
+        let mut event_queue = connection.new_event_queue::<State>();
+        let keyboard = seat.get_keyboard(&event_queue.handle(), 0);
+        state.seats.push(SeatState {
+            wl_keyboard: Some(keyboard),
+            ..Default::default()
+        });
+        event_queue
+            .roundtrip(&mut state)
+            .map_err(KeymapError::from)?; // Get keymap
+        let keymap = state.seats[0].take_layout()?;
+        Ok(Self(keymap))
+    }
+
+    /// Constructs a layout by querying the keymap of the Wayland seat with a
+    /// particular name, such as `"seat0"`.
+    ///
+    /// Use [`Layout::wayland_seat_layouts()`](crate::Layout::wayland_seat_layouts)
+    /// to list the available seats. See
+    /// [`Layout::from_wayland()`](crate::Layout::from_wayland) for the active
+    /// group.
+    pub fn from_wayland_seat_name(name: &str) -> Result<Self, KeyNamesError> {
+        Self::wayland_seat_layouts()?
+            .into_iter()
+            .find(|(seat_name, _)| seat_name == name)
+            .map(|(_, layout)| layout)
+            .ok_or_else(|| KeymapError::UnknownWlSeat(name.to_string()).into())
+    }
+
+    /// Queries the keymap of every Wayland seat, and returns the name of each
+    /// seat along with its layout.
+    ///
+    /// Most compositors have a single seat, but multi-seat setups may have
+    /// different keyboards attached to different seats. Seats without a
+    /// keyboard are omitted, as are seats whose keymap could not be
+    /// constructed unless every seat failed. Seats are named using the
+    /// `wl_seat::Event::Name` event, or the empty string if the compositor
+    /// doesn't send it. See
+    /// [`Layout::from_wayland()`](crate::Layout::from_wayland) for the active
+    /// group.
+    pub fn wayland_seat_layouts() -> Result<Vec<(String, Self)>, KeyNamesError> {
+        let connection = wayland_client::Connection::connect_to_env()
+            .map_err(|_| KeymapError::WaylandConnect)?;
+        let seats = wayland_seat_layouts(&connection)?;
+        Ok(seats
+            .into_iter()
+            .map(|(name, layout)| (name, Self(layout)))
+            .collect())
+    }
+
+    /// Constructs a layout from the `format`, `fd`, and `size` fields of a
+    /// `wl_keyboard::Event::Keymap` event received by the application, with
+    /// group 0 active.
+    ///
+    /// This avoids querying the compositor separately, so the layout always
+    /// matches the keymap that the application received. The compositor sends
+    /// a new keymap event whenever the keymap changes. Use
+    /// [`Layout::set_active_group()`](crate::Layout::set_active_group) to
+    /// update the active group from the `group` field of
+    /// `wl_keyboard::Event::Modifiers`.
+    ///
+    /// # Safety
+    ///
+    /// `fd` must refer to a file containing at least `size` bytes that is not
+    /// truncated while the keymap is being read, as guaranteed by the
+    /// `wl_keyboard` protocol for file descriptors sent by the compositor.
+    pub unsafe fn from_wayland_keymap_fd(
+        format: wayland_client::WEnum<wl_keyboard::KeymapFormat>,
+        fd: OwnedFd,
+        size: u32,
+    ) -> Result<Self, KeyNamesError> {
+        // SAFETY: The caller upholds the requirements.
+        let keymap = unsafe { keymap_from_fd(format, fd, size) }?;
+        Ok(Self::from_xkb_keymap(keymap))
+    }
+}
+
+/// Constructs a keymap from the contents of a `wl_keyboard::Event::Keymap`
+/// event.
+///
+/// # Safety
+///
+/// See [`crate::Layout::from_wayland_keymap_fd()`].
+unsafe fn keymap_from_fd(
+    format: wayland_client::WEnum<wl_keyboard::KeymapFormat>,
+    fd: OwnedFd,
+    size: u32,
+) -> Result<xkb::Keymap, KeymapError> {
+    match format {
+        wayland_client::WEnum::Value(wl_keyboard::KeymapFormat::XkbV1) => {
+            // Construct keymap from file descriptor
+            let ctx = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+            let result = unsafe {
+                xkb::Keymap::new_from_fd(
+                    &ctx,
+                    fd,
+                    size as usize,
+                    xkb::KEYMAP_FORMAT_TEXT_V1,
+                    xkb::KEYMAP_COMPILE_NO_FLAGS,
+                )
+            };
+            result?.ok_or(KeymapError::FailedToCreateKeymap)
+        }
+
+        other => Err(KeymapError::UnsupportedKeymapFormat(other)),
+    }
+}
+
+/// Constructs a keymap in a Wayland environment.
+pub(super) fn new_wayland_keymap() -> Result<Layout, KeymapError> {
+    let connection =
+        wayland_client::Connection::connect_to_env().map_err(|_| KeymapError::WaylandConnect)?;
+    let (_event_queue, _state, _seat, keymap) = wayland_keymap(&connection)?;
+    Ok(keymap)
+}
+
+/// Constructs a keymap using an existing Wayland connection from the first
+/// seat that has one, and gets the active group if the compositor reports it.
+///
+/// The event queue, state, and index of the seat are returned so that the
+/// caller can continue receiving keyboard events.
+pub(super) fn wayland_keymap(
+    connection: &wayland_client::Connection,
+) -> Result<(wayland_client::EventQueue<State>, State, usize, Layout), KeymapError> {
+    let (event_queue, mut state) = wayland_seats(connection)?;
+    let seat = state.default_seat();
+    let layout = state.seats[seat].take_layout()?;
+    Ok((event_queue, state, seat, layout))
+}
+
+/// Binds every `wl_seat` using an existing Wayland connection and gets the
+/// name and keymap of each one.
+fn wayland_seats(
+    connection: &wayland_client::Connection,
+) -> Result<(wayland_client::EventQueue<State>, State), KeymapError> {
+    let display = connection.display();
+
+    // Get the registry.
+    let mut state = State::default();
+    let mut event_queue = connection.new_event_queue::<State>();
+    let qhandle = event_queue.handle();
+    let _registry = display.get_registry(&qhandle, ());
+
+    event_queue.roundtrip(&mut state)?; // Get WlSeat
+    if state.seats.is_empty() {
+        return Err(KeymapError::MissingWlSeat);
+    }
+
+    event_queue.roundtrip(&mut state)?; // Get WlKeyboard and seat names
+    if state.seats.iter().all(|seat| seat.wl_keyboard.is_none()) {
+        return Err(KeymapError::MissingKeyboardCapability);
+    }
+
+    event_queue.roundtrip(&mut state)?; // Get keymap
+    Ok((event_queue, state))
+}
+
+/// Returns the name and layout of each seat.
+fn wayland_seat_layouts(
+    connection: &wayland_client::Connection,
+) -> Result<Vec<(String, Layout)>, KeymapError> {
+    let (_event_queue, mut state) = wayland_seats(connection)?;
+    let mut first_error = None;
+    let mut ret = vec![];
+    for seat in &mut state.seats {
+        if seat.wl_keyboard.is_none() {
+            continue;
+        }
+        match seat.take_layout() {
+            Ok(layout) => ret.push((seat.name.clone().unwrap_or_default(), layout)),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match first_error {
+        Some(e) if ret.is_empty() => Err(e),
+        _ => Ok(ret),
+    }
+}
+
+/// Seats bound by this crate, which are released when this is dropped.
+#[derive(Default)]
+pub(super) struct State {
+    pub(super) seats: Vec<SeatState>,
+}
+
+/// Keyboard state of a single `wl_seat`, which is indexed by the user data of
+/// its `wl_seat` and `wl_keyboard` objects.
+#[derive(Default)]
+pub(super) struct SeatState {
+    /// Seat bound by this crate, or `None` if it is owned by the application.
+    wl_seat: Option<wl_seat::WlSeat>,
+    name: Option<String>,
+    wl_keyboard: Option<wl_keyboard::WlKeyboard>,
+    pub(super) keymap: Option<xkb::Keymap>,
+    pub(super) group: xkb::LayoutIndex,
+    error: Option<KeymapError>,
+}
+
+impl State {
+    /// Returns the index of the first seat with a keymap, or else the first
+    /// seat with a keyboard so that its error can be reported.
+    fn default_seat(&self) -> usize {
+        self.seats
+            .iter()
+            .position(|seat| seat.keymap.is_some())
+            .or_else(|| {
+                self.seats
+                    .iter()
+                    .position(|seat| seat.wl_keyboard.is_some())
+            })
+            .unwrap_or(0)
+    }
+}
+
+impl Drop for State {
+    /// Releases the objects bound by this state, which is necessary when
+    /// using a connection that outlives it. Objects whose version doesn't
+    /// support releasing are left alone.
+    fn drop(&mut self) {
+        for seat in &self.seats {
+            if let Some(keyboard) = &seat.wl_keyboard {
+                if keyboard.version() >= 3 {
+                    keyboard.release();
+                }
+            }
+            if let Some(seat) = &seat.wl_seat {
+                if seat.version() >= 5 {
+                    seat.release();
+                }
+            }
+        }
+    }
+}
+
+impl SeatState {
+    /// Takes the most recently received keymap, or returns the error that
+    /// prevented it from being received.
+    fn take_layout(&mut self) -> Result<Layout, KeymapError> {
+        match self.keymap.take() {
+            Some(keymap) => Ok(Layout {
+                keymap,
+                group: self.group,
+            }),
+            None => Err(self
+                .error
+                .take()
+                .unwrap_or(KeymapError::FailedToCreateKeymap)),
+        }
+    }
+}
+
+impl wayland_client::Dispatch<wl_registry::WlRegistry, ()> for State {
+    fn event(
+        state: &mut Self,
+        registry: &wl_registry::WlRegistry,
+        event: wl_registry::Event,
+        _: &(),
+        _: &wayland_client::Connection,
+        qh: &wayland_client::QueueHandle<Self>,
+    ) {
+        if let wl_registry::Event::Global {
+            name,
+            interface,
+            version,
+        } = event
+        {
+            if interface.as_str() == "wl_seat" {
+                let index = state.seats.len();
+                let seat = registry.bind::<wl_seat::WlSeat, _, _>(name, version, qh, index);
+                state.seats.push(SeatState {
+                    wl_seat: Some(seat),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+}
+
+impl wayland_client::Dispatch<wl_seat::WlSeat, usize> for State {
+    fn event(
+        state: &mut Self,
+        seat: &wl_seat::WlSeat,
+        event: wl_seat::Event,
+        &index: &usize,
+        _: &wayland_client::Connection,
+        qh: &wayland_client::QueueHandle<Self>,
+    ) {
+        match event {
+            wl_seat::Event::Capabilities {
+                capabilities: wayland_client::WEnum::Value(capabilities),
+            } => {
+                let seat_state = &mut state.seats[index];
+                if capabilities.contains(wl_seat::Capability::Keyboard)
+                    && seat_state.wl_keyboard.is_none()
+                {
+                    seat_state.wl_keyboard = Some(seat.get_keyboard(qh, index));
+                }
+            }
+
+            // Only sent by `wl_seat` version 2 and later
+            wl_seat::Event::Name { name } => state.seats[index].name = Some(name),
+
+            _ => (),
+        }
+    }
+}
+
+impl wayland_client::Dispatch<wl_keyboard::WlKeyboard, usize> for State {
+    fn event(
+        state: &mut Self,
+        _: &wl_keyboard::WlKeyboard,
+        event: wl_keyboard::Event,
+        &index: &usize,
+        _: &wayland_client::Connection,
+        _: &wayland_client::QueueHandle<Self>,
+    ) {
+        let seat_state = &mut state.seats[index];
+        match event {
+            wl_keyboard::Event::Keymap { format, fd, size } => {
+                // SAFETY: The file descriptor and size come from the
+                // compositor.
+                match unsafe { keymap_from_fd(format, fd, size) } {
+                    Ok(keymap) => seat_state.keymap = Some(keymap),
+                    Err(e) => seat_state.error = Some(e),
+                }
+            }
+
+            // Only sent to clients with keyboard focus
+            wl_keyboard::Event::Modifiers { group, .. } => seat_state.group = group,
+
+            _ => (),
+        }
+    }
+}
+
+// Ignore events from other object types
+wayland_client::delegate_noop!(State: ignore wayland_client::protocol::wl_compositor::WlCompositor);
+wayland_client::delegate_noop!(State: ignore wayland_client::protocol::wl_surface::WlSurface);
+wayland_client::delegate_noop!(State: ignore wayland_client::protocol::wl_shm::WlShm);
+wayland_client::delegate_noop!(State: ignore wayland_client::protocol::wl_shm_pool::WlShmPool);
+wayland_client::delegate_noop!(State: ignore wayland_client::protocol::wl_buffer::WlBuffer);
@@ -0,0 +1,183 @@
+use std::ffi::c_void;
+use std::mem::ManuallyDrop;
+
+use xcb::xinput;
+use xkb::x11::{MIN_MAJOR_XKB_VERSION, MIN_MINOR_XKB_VERSION};
+use xkbcommon::xkb;
+
+use super::{KeymapError, Layout};
+use crate::KeyNamesError;
+
+impl crate::Layout {
+    /// Constructs a layout by querying the keymap and active group from X11.
+    pub fn from_x11() -> Result<Self, KeyNamesError> {
+        Ok(Self(new_x11_keymap()?))
+    }
+
+    /// Constructs a layout by querying the keymap and active group using an
+    /// existing X11 connection, such as the one used by the application's
+    /// windowing library, instead of opening a new one.
+    pub fn from_x11_connection(connection: &xcb::Connection) -> Result<Self, KeyNamesError> {
+        Ok(Self(x11_keymap(connection)?))
+    }
+
+    /// Constructs a layout by querying the keymap and active group using an
+    /// existing `xcb_connection_t` pointer, such as the `connection` field of
+    /// `raw_window_handle::XcbDisplayHandle`.
+    ///
+    /// The connection is not closed. Returns an error if `connection` is null.
+    ///
+    /// # Safety
+    ///
+    /// `connection` must be null or point to a valid `xcb_connection_t` that
+    /// is not in an error state and remains open for the duration of this
+    /// call.
+    pub unsafe fn from_raw_xcb_connection(connection: *mut c_void) -> Result<Self, KeyNamesError> {
+        if connection.is_null() {
+            return Err(KeymapError::X11Connect.into());
+        }
+        // SAFETY: The caller guarantees that the connection is valid, and
+        // `ManuallyDrop` prevents it from being closed.
+        let connection =
+            ManuallyDrop::new(unsafe { xcb::Connection::from_raw_conn(connection.cast()) });
+        Self::from_x11_connection(&connection)
+    }
+
+    /// Constructs a layout by querying the keymap and active group of a
+    /// particular X11 keyboard device, such as an external keyboard that uses
+    /// a different layout from the laptop's built-in keyboard.
+    ///
+    /// Use [`Layout::x11_keyboard_devices()`](crate::Layout::x11_keyboard_devices)
+    /// to list the available devices.
+    /// [`Layout::from_x11()`](crate::Layout::from_x11) uses the core keyboard
+    /// device, which is the master keyboard that all keyboards are attached to
+    /// by default.
+    pub fn from_x11_device(device_id: i32) -> Result<Self, KeyNamesError> {
+        let (connection, _) =
+            xcb::Connection::connect(None).map_err(|_| KeymapError::X11Connect)?;
+        setup_xkb_extension(&connection);
+        Ok(Self(x11_device_keymap(&connection, device_id)?))
+    }
+
+    /// Lists the keyboard devices known to the X server using the XInput 2
+    /// extension.
+    ///
+    /// This includes master keyboards, which combine the input of all
+    /// attached keyboards, and slave keyboards, which correspond to physical
+    /// devices. Some slave keyboards, such as power buttons, have no keys that
+    /// produce symbols.
+    pub fn x11_keyboard_devices() -> Result<Vec<X11KeyboardDevice>, KeyNamesError> {
+        let (connection, _) =
+            xcb::Connection::connect_with_extensions(None, &[], &[xcb::Extension::Input])
+                .map_err(|_| KeymapError::X11Connect)?;
+        Ok(x11_keyboard_devices(&connection)?)
+    }
+}
+
+/// Keyboard device known to the X server, as returned by
+/// [`Layout::x11_keyboard_devices()`](crate::Layout::x11_keyboard_devices).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct X11KeyboardDevice {
+    /// Device ID, which can be passed to
+    /// [`Layout::from_x11_device()`](crate::Layout::from_x11_device).
+    pub id: i32,
+    /// Name of the device, such as "AT Translated Set 2 keyboard".
+    pub name: String,
+    /// Whether this is a master keyboard, which combines the input of the
+    /// slave keyboards attached to it, rather than a physical device.
+    pub is_master: bool,
+    /// Whether the device is enabled.
+    pub enabled: bool,
+}
+
+/// Constructs a keymap in an X11 environment.
+pub(super) fn new_x11_keymap() -> Result<Layout, KeymapError> {
+    let (connection, _) = xcb::Connection::connect(None).map_err(|_| KeymapError::X11Connect)?;
+    x11_keymap(&connection)
+}
+
+/// Constructs a keymap for the core keyboard using an existing X11
+/// connection, and gets the active group.
+pub(super) fn x11_keymap(connection: &xcb::Connection) -> Result<Layout, KeymapError> {
+    setup_xkb_extension(connection);
+    let device_id = xkb::x11::get_core_keyboard_device_id(connection);
+    x11_device_keymap(connection, device_id)
+}
+
+/// Constructs a keymap for a particular keyboard device using an existing X11
+/// connection, and gets the active group. The XKB extension must already be
+/// set up.
+fn x11_device_keymap(connection: &xcb::Connection, device_id: i32) -> Result<Layout, KeymapError> {
+    let ctx = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+
+    let keymap = xkb::x11::keymap_new_from_device(&ctx, connection, device_id, 0);
+    // The keymap is null if the device doesn't exist or isn't a keyboard.
+    if keymap.get_raw_ptr().is_null() {
+        return Err(KeymapError::FailedToCreateKeymap);
+    }
+    let group = xkb::x11::state_new_from_device(&keymap, connection, device_id)
+        .serialize_layout(xkb::STATE_LAYOUT_EFFECTIVE);
+
+    Ok(Layout { keymap, group })
+}
+
+/// Sets up the XKB extension, which is required before querying keymaps.
+fn setup_xkb_extension(connection: &xcb::Connection) {
+    // This code is modified from Frinksy's `keyboard-keynames` crate:
+    // https://gitlab.com/Frinksy/keyboard-keynames/-/blob/master/src/platform/unix/key_layout.rs
+
+    let mut major_xkb_version_out = 0;
+    let mut minor_xkb_version_out = 0;
+    let mut base_event_out = 0;
+    let mut base_error_out = 0;
+
+    let _ = xkb::x11::setup_xkb_extension(
+        connection,
+        MIN_MAJOR_XKB_VERSION,
+        MIN_MINOR_XKB_VERSION,
+        xkb::x11::SetupXkbExtensionFlags::NoFlags,
+        &mut major_xkb_version_out,
+        &mut minor_xkb_version_out,
+        &mut base_event_out,
+        &mut base_error_out,
+    );
+}
+
+/// Lists the master and slave keyboard devices using XInput 2.
+fn x11_keyboard_devices(
+    connection: &xcb::Connection,
+) -> Result<Vec<X11KeyboardDevice>, KeymapError> {
+    // XInput 2 requests fail unless the client first announces which version
+    // it supports.
+    let cookie = connection.send_request(&xinput::XiQueryVersion {
+        major_version: 2,
+        minor_version: 0,
+    });
+    connection
+        .wait_for_reply(cookie)
+        .map_err(|_| KeymapError::MissingXInput)?;
+
+    let cookie = connection.send_request(&xinput::XiQueryDevice {
+        device: xinput::Device::All,
+    });
+    let reply = connection
+        .wait_for_reply(cookie)
+        .map_err(|_| KeymapError::MissingXInput)?;
+    let devices = reply
+        .infos()
+        .filter_map(|info| {
+            let is_master = match info.r#type() {
+                xinput::DeviceType::MasterKeyboard => true,
+                xinput::DeviceType::SlaveKeyboard => false,
+                _ => return None,
+            };
+            Some(X11KeyboardDevice {
+                id: info.device().id() as i32,
+                name: info.name().to_utf8().into_owned(),
+                is_master,
+                enabled: info.enabled(),
+            })
+        })
+        .collect();
+    Ok(devices)
+}
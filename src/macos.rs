@@ -1,103 +1,68 @@
-use winit::keyboard::{KeyCode, NamedKey, PhysicalKey};
+#[cfg(feature = "winit")]
+use winit::keyboard::{NativeKey, NativeKeyCode, PhysicalKey};
+#[cfg(feature = "winit")]
+use winit::platform::scancode::PhysicalKeyExtScancode;
 
-pub const ALT_STR: &str = "Option";
-pub const LOGO_STR: &str = "Cmd";
-pub const MODIFIERS_ORDER: &str = "casm"; // Ctrl + Alt + Shift + Meta
+#[cfg(feature = "winit")]
+use crate::platform::macos::hardcoded_physical_key_name;
+#[cfg(feature = "winit")]
+use crate::qwerty;
+use crate::{KeyNamesError, Level};
 
-pub fn os_specific_key_name(key: NamedKey) -> Option<&'static str> {
-    match key {
-        NamedKey::Alt => Some("Option"),
-        NamedKey::AltGraph => Some("Right Option"),
-        NamedKey::ArrowDown => Some("Down"),
-        NamedKey::ArrowLeft => Some("Left"),
-        NamedKey::ArrowRight => Some("Right"),
-        NamedKey::ArrowUp => Some("Up"),
-        NamedKey::Backspace => Some("Delete"),
-        NamedKey::Delete => Some("Forward Delete"),
-        NamedKey::Enter => Some("Return"),
-        NamedKey::Escape => Some("Esc"),
-        NamedKey::Super => Some("Command"),
-        _ => None,
-    }
+#[cfg(feature = "winit")]
+pub fn native_key_code(sc: u32) -> Option<NativeKeyCode> {
+    u16::try_from(sc).ok().map(NativeKeyCode::MacOS)
 }
 
-pub fn try_physical_key_name(physical_key: PhysicalKey) -> Option<String> {
-    let s = match physical_key {
-        PhysicalKey::Code(key_code) => match key_code {
-            KeyCode::Backquote => "`",
-            KeyCode::Backslash => "\\",
-            KeyCode::BracketLeft => "[",
-            KeyCode::BracketRight => "]",
-            KeyCode::Comma => ",",
-            KeyCode::Digit0 => "0",
-            KeyCode::Digit1 => "1",
-            KeyCode::Digit2 => "2",
-            KeyCode::Digit3 => "3",
-            KeyCode::Digit4 => "4",
-            KeyCode::Digit5 => "5",
-            KeyCode::Digit6 => "6",
-            KeyCode::Digit7 => "7",
-            KeyCode::Digit8 => "8",
-            KeyCode::Digit9 => "9",
-            KeyCode::Equal => "=",
+#[cfg(feature = "winit")]
+pub fn native_key(sc: u32) -> Option<NativeKey> {
+    u16::try_from(sc).ok().map(NativeKey::MacOS)
+}
 
-            KeyCode::KeyA => "A",
-            KeyCode::KeyB => "B",
-            KeyCode::KeyC => "C",
-            KeyCode::KeyD => "D",
-            KeyCode::KeyE => "E",
-            KeyCode::KeyF => "F",
-            KeyCode::KeyG => "G",
-            KeyCode::KeyH => "H",
-            KeyCode::KeyI => "I",
-            KeyCode::KeyJ => "J",
-            KeyCode::KeyK => "K",
-            KeyCode::KeyL => "L",
-            KeyCode::KeyM => "M",
-            KeyCode::KeyN => "N",
-            KeyCode::KeyO => "O",
-            KeyCode::KeyP => "P",
-            KeyCode::KeyQ => "Q",
-            KeyCode::KeyR => "R",
-            KeyCode::KeyS => "S",
-            KeyCode::KeyT => "T",
-            KeyCode::KeyU => "U",
-            KeyCode::KeyV => "V",
-            KeyCode::KeyW => "W",
-            KeyCode::KeyX => "X",
-            KeyCode::KeyY => "Y",
-            KeyCode::KeyZ => "Z",
-            KeyCode::Minus => "-",
-            KeyCode::Period => ".",
-            KeyCode::Quote => "'",
-            KeyCode::Semicolon => ";",
-            KeyCode::Slash => "/",
+#[derive(Debug, Default, Clone)]
+pub struct Layout;
 
-            KeyCode::AltLeft => "Option",
-            KeyCode::AltRight => "Right Option",
-            KeyCode::ControlLeft => "Control",
-            KeyCode::ControlRight => "Right Control",
-            KeyCode::SuperLeft => "Command",
-            KeyCode::SuperRight => "Right Command", // doesn't exist anyway
-            KeyCode::ShiftLeft => "Shift",
-            KeyCode::ShiftRight => "Right Shift",
+impl Layout {
+    pub fn new() -> Result<Self, KeyNamesError> {
+        Ok(Layout)
+    }
 
-            KeyCode::Backspace => "Delete",
-            KeyCode::Enter => "Return",
+    #[cfg(feature = "winit")]
+    pub fn try_physical_key_name(&self, physical_key: PhysicalKey) -> Option<String> {
+        hardcoded_physical_key_name(physical_key).map(str::to_string)
+    }
 
-            KeyCode::ArrowDown => "Down",
-            KeyCode::ArrowLeft => "Left",
-            KeyCode::ArrowRight => "Right",
-            KeyCode::ArrowUp => "Up",
-            KeyCode::Delete => "Forward Delete",
+    #[cfg(feature = "winit")]
+    pub fn physical_key_for_char(&self, c: char) -> Option<(PhysicalKey, Level)> {
+        qwerty::physical_key_for_char(hardcoded_physical_key_name, c)
+    }
 
-            KeyCode::Escape => "Esc",
+    #[cfg(feature = "winit")]
+    pub fn try_scancode_name(&self, sc: u32) -> Option<String> {
+        self.try_physical_key_name(PhysicalKey::from_scancode(sc))
+    }
 
-            _ => return None,
-        },
+    #[cfg(feature = "winit")]
+    pub fn scancode_for_char(&self, c: char) -> Option<(u32, Level)> {
+        let (physical_key, level) = self.physical_key_for_char(c)?;
+        Some((physical_key.to_scancode()?, level))
+    }
 
-        PhysicalKey::Unidentified(_) => return None,
-    };
+    // The hard-coded key names are keyed by winit's `KeyCode`, so without
+    // winit there is no way to name a scancode. This is documented on
+    // `crate::Layout::scancode_name()`.
+    #[cfg(not(feature = "winit"))]
+    pub fn try_scancode_name(&self, _sc: u32) -> Option<String> {
+        None
+    }
 
-    Some(s.to_string())
+    #[cfg(not(feature = "winit"))]
+    pub fn scancode_for_char(&self, _c: char) -> Option<(u32, Level)> {
+        None
+    }
+
+    #[cfg(feature = "winit")]
+    pub fn unidentified_physical_keys(&self) -> Vec<PhysicalKey> {
+        vec![]
+    }
 }
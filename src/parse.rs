@@ -0,0 +1,783 @@
+//! Parsing of key names produced by this crate.
+
+use winit::keyboard::{Key, KeyCode, NamedKey, NativeKey, NativeKeyCode, PhysicalKey};
+
+use crate::{os, style, Platform};
+
+/// Every key code known to winit, in declaration order.
+pub const KEY_CODES: [KeyCode; 194] = {
+    use KeyCode::*;
+    [
+        Backquote,
+        Backslash,
+        BracketLeft,
+        BracketRight,
+        Comma,
+        Digit0,
+        Digit1,
+        Digit2,
+        Digit3,
+        Digit4,
+        Digit5,
+        Digit6,
+        Digit7,
+        Digit8,
+        Digit9,
+        Equal,
+        IntlBackslash,
+        IntlRo,
+        IntlYen,
+        KeyA,
+        KeyB,
+        KeyC,
+        KeyD,
+        KeyE,
+        KeyF,
+        KeyG,
+        KeyH,
+        KeyI,
+        KeyJ,
+        KeyK,
+        KeyL,
+        KeyM,
+        KeyN,
+        KeyO,
+        KeyP,
+        KeyQ,
+        KeyR,
+        KeyS,
+        KeyT,
+        KeyU,
+        KeyV,
+        KeyW,
+        KeyX,
+        KeyY,
+        KeyZ,
+        Minus,
+        Period,
+        Quote,
+        Semicolon,
+        Slash,
+        AltLeft,
+        AltRight,
+        Backspace,
+        CapsLock,
+        ContextMenu,
+        ControlLeft,
+        ControlRight,
+        Enter,
+        SuperLeft,
+        SuperRight,
+        ShiftLeft,
+        ShiftRight,
+        Space,
+        Tab,
+        Convert,
+        KanaMode,
+        Lang1,
+        Lang2,
+        Lang3,
+        Lang4,
+        Lang5,
+        NonConvert,
+        Delete,
+        End,
+        Help,
+        Home,
+        Insert,
+        PageDown,
+        PageUp,
+        ArrowDown,
+        ArrowLeft,
+        ArrowRight,
+        ArrowUp,
+        NumLock,
+        Numpad0,
+        Numpad1,
+        Numpad2,
+        Numpad3,
+        Numpad4,
+        Numpad5,
+        Numpad6,
+        Numpad7,
+        Numpad8,
+        Numpad9,
+        NumpadAdd,
+        NumpadBackspace,
+        NumpadClear,
+        NumpadClearEntry,
+        NumpadComma,
+        NumpadDecimal,
+        NumpadDivide,
+        NumpadEnter,
+        NumpadEqual,
+        NumpadHash,
+        NumpadMemoryAdd,
+        NumpadMemoryClear,
+        NumpadMemoryRecall,
+        NumpadMemoryStore,
+        NumpadMemorySubtract,
+        NumpadMultiply,
+        NumpadParenLeft,
+        NumpadParenRight,
+        NumpadStar,
+        NumpadSubtract,
+        Escape,
+        Fn,
+        FnLock,
+        PrintScreen,
+        ScrollLock,
+        Pause,
+        BrowserBack,
+        BrowserFavorites,
+        BrowserForward,
+        BrowserHome,
+        BrowserRefresh,
+        BrowserSearch,
+        BrowserStop,
+        Eject,
+        LaunchApp1,
+        LaunchApp2,
+        LaunchMail,
+        MediaPlayPause,
+        MediaSelect,
+        MediaStop,
+        MediaTrackNext,
+        MediaTrackPrevious,
+        Power,
+        Sleep,
+        AudioVolumeDown,
+        AudioVolumeMute,
+        AudioVolumeUp,
+        WakeUp,
+        Meta,
+        Hyper,
+        Turbo,
+        Abort,
+        Resume,
+        Suspend,
+        Again,
+        Copy,
+        Cut,
+        Find,
+        Open,
+        Paste,
+        Props,
+        Select,
+        Undo,
+        Hiragana,
+        Katakana,
+        F1,
+        F2,
+        F3,
+        F4,
+        F5,
+        F6,
+        F7,
+        F8,
+        F9,
+        F10,
+        F11,
+        F12,
+        F13,
+        F14,
+        F15,
+        F16,
+        F17,
+        F18,
+        F19,
+        F20,
+        F21,
+        F22,
+        F23,
+        F24,
+        F25,
+        F26,
+        F27,
+        F28,
+        F29,
+        F30,
+        F31,
+        F32,
+        F33,
+        F34,
+        F35,
+    ]
+};
+
+/// Every named key known to winit, in declaration order.
+pub const NAMED_KEYS: [NamedKey; 306] = {
+    use NamedKey::*;
+    [
+        Alt,
+        AltGraph,
+        CapsLock,
+        Control,
+        Fn,
+        FnLock,
+        NumLock,
+        ScrollLock,
+        Shift,
+        Symbol,
+        SymbolLock,
+        Meta,
+        Hyper,
+        Super,
+        Enter,
+        Tab,
+        Space,
+        ArrowDown,
+        ArrowLeft,
+        ArrowRight,
+        ArrowUp,
+        End,
+        Home,
+        PageDown,
+        PageUp,
+        Backspace,
+        Clear,
+        Copy,
+        CrSel,
+        Cut,
+        Delete,
+        EraseEof,
+        ExSel,
+        Insert,
+        Paste,
+        Redo,
+        Undo,
+        Accept,
+        Again,
+        Attn,
+        Cancel,
+        ContextMenu,
+        Escape,
+        Execute,
+        Find,
+        Help,
+        Pause,
+        Play,
+        Props,
+        Select,
+        ZoomIn,
+        ZoomOut,
+        BrightnessDown,
+        BrightnessUp,
+        Eject,
+        LogOff,
+        Power,
+        PowerOff,
+        PrintScreen,
+        Hibernate,
+        Standby,
+        WakeUp,
+        AllCandidates,
+        Alphanumeric,
+        CodeInput,
+        Compose,
+        Convert,
+        FinalMode,
+        GroupFirst,
+        GroupLast,
+        GroupNext,
+        GroupPrevious,
+        ModeChange,
+        NextCandidate,
+        NonConvert,
+        PreviousCandidate,
+        Process,
+        SingleCandidate,
+        HangulMode,
+        HanjaMode,
+        JunjaMode,
+        Eisu,
+        Hankaku,
+        Hiragana,
+        HiraganaKatakana,
+        KanaMode,
+        KanjiMode,
+        Katakana,
+        Romaji,
+        Zenkaku,
+        ZenkakuHankaku,
+        Soft1,
+        Soft2,
+        Soft3,
+        Soft4,
+        ChannelDown,
+        ChannelUp,
+        Close,
+        MailForward,
+        MailReply,
+        MailSend,
+        MediaClose,
+        MediaFastForward,
+        MediaPause,
+        MediaPlay,
+        MediaPlayPause,
+        MediaRecord,
+        MediaRewind,
+        MediaStop,
+        MediaTrackNext,
+        MediaTrackPrevious,
+        New,
+        Open,
+        Print,
+        Save,
+        SpellCheck,
+        Key11,
+        Key12,
+        AudioBalanceLeft,
+        AudioBalanceRight,
+        AudioBassBoostDown,
+        AudioBassBoostToggle,
+        AudioBassBoostUp,
+        AudioFaderFront,
+        AudioFaderRear,
+        AudioSurroundModeNext,
+        AudioTrebleDown,
+        AudioTrebleUp,
+        AudioVolumeDown,
+        AudioVolumeUp,
+        AudioVolumeMute,
+        MicrophoneToggle,
+        MicrophoneVolumeDown,
+        MicrophoneVolumeUp,
+        MicrophoneVolumeMute,
+        SpeechCorrectionList,
+        SpeechInputToggle,
+        LaunchApplication1,
+        LaunchApplication2,
+        LaunchCalendar,
+        LaunchContacts,
+        LaunchMail,
+        LaunchMediaPlayer,
+        LaunchMusicPlayer,
+        LaunchPhone,
+        LaunchScreenSaver,
+        LaunchSpreadsheet,
+        LaunchWebBrowser,
+        LaunchWebCam,
+        LaunchWordProcessor,
+        BrowserBack,
+        BrowserFavorites,
+        BrowserForward,
+        BrowserHome,
+        BrowserRefresh,
+        BrowserSearch,
+        BrowserStop,
+        AppSwitch,
+        Call,
+        Camera,
+        CameraFocus,
+        EndCall,
+        GoBack,
+        GoHome,
+        HeadsetHook,
+        LastNumberRedial,
+        Notification,
+        MannerMode,
+        VoiceDial,
+        TV,
+        TV3DMode,
+        TVAntennaCable,
+        TVAudioDescription,
+        TVAudioDescriptionMixDown,
+        TVAudioDescriptionMixUp,
+        TVContentsMenu,
+        TVDataService,
+        TVInput,
+        TVInputComponent1,
+        TVInputComponent2,
+        TVInputComposite1,
+        TVInputComposite2,
+        TVInputHDMI1,
+        TVInputHDMI2,
+        TVInputHDMI3,
+        TVInputHDMI4,
+        TVInputVGA1,
+        TVMediaContext,
+        TVNetwork,
+        TVNumberEntry,
+        TVPower,
+        TVRadioService,
+        TVSatellite,
+        TVSatelliteBS,
+        TVSatelliteCS,
+        TVSatelliteToggle,
+        TVTerrestrialAnalog,
+        TVTerrestrialDigital,
+        TVTimer,
+        AVRInput,
+        AVRPower,
+        ColorF0Red,
+        ColorF1Green,
+        ColorF2Yellow,
+        ColorF3Blue,
+        ColorF4Grey,
+        ColorF5Brown,
+        ClosedCaptionToggle,
+        Dimmer,
+        DisplaySwap,
+        DVR,
+        Exit,
+        FavoriteClear0,
+        FavoriteClear1,
+        FavoriteClear2,
+        FavoriteClear3,
+        FavoriteRecall0,
+        FavoriteRecall1,
+        FavoriteRecall2,
+        FavoriteRecall3,
+        FavoriteStore0,
+        FavoriteStore1,
+        FavoriteStore2,
+        FavoriteStore3,
+        Guide,
+        GuideNextDay,
+        GuidePreviousDay,
+        Info,
+        InstantReplay,
+        Link,
+        ListProgram,
+        LiveContent,
+        Lock,
+        MediaApps,
+        MediaAudioTrack,
+        MediaLast,
+        MediaSkipBackward,
+        MediaSkipForward,
+        MediaStepBackward,
+        MediaStepForward,
+        MediaTopMenu,
+        NavigateIn,
+        NavigateNext,
+        NavigateOut,
+        NavigatePrevious,
+        NextFavoriteChannel,
+        NextUserProfile,
+        OnDemand,
+        Pairing,
+        PinPDown,
+        PinPMove,
+        PinPToggle,
+        PinPUp,
+        PlaySpeedDown,
+        PlaySpeedReset,
+        PlaySpeedUp,
+        RandomToggle,
+        RcLowBattery,
+        RecordSpeedNext,
+        RfBypass,
+        ScanChannelsToggle,
+        ScreenModeNext,
+        Settings,
+        SplitScreenToggle,
+        STBInput,
+        STBPower,
+        Subtitle,
+        Teletext,
+        VideoModeNext,
+        Wink,
+        ZoomToggle,
+        F1,
+        F2,
+        F3,
+        F4,
+        F5,
+        F6,
+        F7,
+        F8,
+        F9,
+        F10,
+        F11,
+        F12,
+        F13,
+        F14,
+        F15,
+        F16,
+        F17,
+        F18,
+        F19,
+        F20,
+        F21,
+        F22,
+        F23,
+        F24,
+        F25,
+        F26,
+        F27,
+        F28,
+        F29,
+        F30,
+        F31,
+        F32,
+        F33,
+        F34,
+        F35,
+    ]
+};
+
+/// Parses a physical key name by comparing it against the name of each
+/// candidate key, and then against the `Debug` name and symbol of each key
+/// code.
+///
+/// Exact matches are preferred over case-insensitive matches, and earlier
+/// candidates are preferred over later ones.
+pub fn parse_physical_key(
+    candidates: impl IntoIterator<Item = PhysicalKey>,
+    name_of: impl Fn(PhysicalKey) -> String,
+    s: &str,
+) -> Option<PhysicalKey> {
+    if s == "<unknown>" {
+        return Some(PhysicalKey::Unidentified(NativeKeyCode::Unidentified));
+    }
+    if let Some(sc) = parse_scancode(s) {
+        return os::native_key_code(sc).map(PhysicalKey::Unidentified);
+    }
+
+    let names = candidates
+        .into_iter()
+        .map(|physical_key| (physical_key, name_of(physical_key)))
+        .chain(
+            KEY_CODES
+                .iter()
+                .map(|&key_code| (PhysicalKey::Code(key_code), format!("{key_code:?}"))),
+        )
+        .chain(KEY_CODES.iter().filter_map(|&key_code| {
+            let symbol = style::key_code_symbol(key_code)?;
+            Some((PhysicalKey::Code(key_code), symbol.to_string()))
+        }));
+    find_by_name(names, s)
+}
+
+/// Parses a virtual key name produced by [`Platform::key_name()`].
+pub fn parse_key(platform: Platform, s: &str) -> Option<Key> {
+    if s == "<unknown>" {
+        return Some(Key::Unidentified(NativeKey::Unidentified));
+    }
+    if let Some(sc) = parse_scancode(s) {
+        return os::native_key(sc).map(Key::Unidentified);
+    }
+
+    // The platform's own names take priority over `Debug` names, and names
+    // from other platforms are accepted last. For example, "Delete" refers to
+    // `Backspace` on macOS but to `Delete` everywhere else.
+    let other_platforms = Platform::ALL.into_iter().filter(|&p| p != platform);
+    let other_platform_names = other_platforms.flat_map(os_specific_key_names);
+    let debug_names = NAMED_KEYS
+        .iter()
+        .map(|&named_key| (Key::Named(named_key), format!("{named_key:?}")));
+    let symbols = NAMED_KEYS.iter().filter_map(|&named_key| {
+        let symbol = style::named_key_symbol(named_key)?;
+        Some((Key::Named(named_key), symbol.to_string()))
+    });
+    let names = os_specific_key_names(platform)
+        .chain(debug_names)
+        .chain(symbols)
+        .chain(other_platform_names);
+    if let Some(key) = find_by_name(names, s) {
+        return Some(key);
+    }
+
+    // Any other single character is a character key. Letters are named using
+    // uppercase but produced as lowercase by an unmodified key press.
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(_), None) => Some(Key::Character(s.to_ascii_lowercase().into())),
+        _ => None,
+    }
+}
+
+/// Returns the OS-specific names of named keys on a platform.
+fn os_specific_key_names(platform: Platform) -> impl Iterator<Item = (Key, String)> {
+    NAMED_KEYS.iter().filter_map(move |&named_key| {
+        let name = platform.os_specific_key_name(named_key)?;
+        Some((Key::Named(named_key), name.to_string()))
+    })
+}
+
+/// Parses the `SC{n}` format used for keys with no known name.
+fn parse_scancode(s: &str) -> Option<u32> {
+    let digits = s.strip_prefix("SC")?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Returns the first key whose name matches `s` exactly, or else the first key
+/// whose name matches `s` ignoring case and the spaces between words (so that
+/// "page up" and "pageup" match "PageUp", but "f 1" does not match "F1").
+///
+/// Spaces that don't separate words are significant so that a list of keys
+/// separated by spaces, such as "C-f 1", is not mistaken for a single key.
+fn find_by_name<K: Clone>(names: impl IntoIterator<Item = (K, String)>, s: &str) -> Option<K> {
+    let names: Vec<(K, String)> = names.into_iter().collect();
+    let exact = names.iter().find(|(_, name)| name == s);
+    let s = s.to_lowercase();
+    exact
+        .or_else(|| {
+            names
+                .iter()
+                .find(|(_, name)| loose_names(name).contains(&s))
+        })
+        .map(|(key, _)| key.clone())
+}
+
+/// Returns the lowercase forms of a name that match ignoring case and the
+/// spaces between words: the name itself, the name without spaces, and the
+/// name with spaces between the words of a camel-case name such as "PageUp".
+fn loose_names(name: &str) -> [String; 3] {
+    let chars: Vec<char> = name.chars().collect();
+    let mut spaced = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        let starts_word = c.is_uppercase()
+            && i > 0
+            && chars[i - 1].is_lowercase()
+            && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+        if starts_word {
+            spaced.push(' ');
+        }
+        spaced.push(c);
+    }
+    [
+        name.to_lowercase(),
+        name.replace(' ', "").to_lowercase(),
+        spaced.to_lowercase(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::keyboard::SmolStr;
+
+    use super::*;
+    #[cfg(target_os = "linux")]
+    use crate::Backend;
+    use crate::Layout;
+
+    #[cfg(target_os = "linux")]
+    fn assert_physical_key_names_round_trip(layout: &Layout) {
+        for key_code in KEY_CODES {
+            let name = layout.physical_key_name(PhysicalKey::Code(key_code));
+            let parsed = layout
+                .parse_physical_key(&name)
+                .unwrap_or_else(|| panic!("failed to parse {name:?} for {key_code:?}"));
+            // Some names are shared by multiple keys, such as "1" for `Digit1`
+            // and `Numpad1`, so compare names instead of keys.
+            assert_eq!(layout.physical_key_name(parsed), name, "{key_code:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_physical_key_names_round_trip_us() {
+        let layout = Layout::from_backend(Backend::Static).unwrap();
+        assert_physical_key_names_round_trip(&layout);
+        assert_eq!(
+            layout.parse_physical_key("Z"),
+            Some(PhysicalKey::Code(KeyCode::KeyZ)),
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_physical_key_names_round_trip_de() {
+        let layout = Layout::from_xkb_layout_name("de").unwrap();
+        assert_physical_key_names_round_trip(&layout);
+        assert_eq!(
+            layout.parse_physical_key("Z"),
+            Some(PhysicalKey::Code(KeyCode::KeyY)),
+        );
+        assert_eq!(
+            layout.parse_physical_key("ß"),
+            Some(PhysicalKey::Code(KeyCode::Minus)),
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_parse_physical_key_fallbacks() {
+        let layout = Layout::from_backend(Backend::Static).unwrap();
+        for (s, expected) in [
+            ("KeyA", PhysicalKey::Code(KeyCode::KeyA)),
+            ("keya", PhysicalKey::Code(KeyCode::KeyA)),
+            ("page up", PhysicalKey::Code(KeyCode::PageUp)),
+            ("pageup", PhysicalKey::Code(KeyCode::PageUp)),
+            ("↩", PhysicalKey::Code(KeyCode::Enter)),
+            (
+                "<unknown>",
+                PhysicalKey::Unidentified(NativeKeyCode::Unidentified),
+            ),
+            ("SC300", PhysicalKey::Unidentified(NativeKeyCode::Xkb(300))),
+            (
+                "SC4294967295",
+                PhysicalKey::Unidentified(NativeKeyCode::Xkb(u32::MAX)),
+            ),
+        ] {
+            assert_eq!(layout.parse_physical_key(s), Some(expected), "{s:?}");
+        }
+        for s in [
+            "",
+            "SC",
+            "SC-1",
+            "SC1a",
+            "SC4294967296",
+            "Nonexistent",
+            "Key A",
+            "F 1",
+        ] {
+            assert_eq!(layout.parse_physical_key(s), None, "{s:?}");
+        }
+        // Out-of-range scancodes have no name, but must not panic.
+        let sc = PhysicalKey::Unidentified(NativeKeyCode::Xkb(u32::MAX));
+        assert_eq!(layout.physical_key_name(sc), "SC4294967295");
+    }
+
+    #[test]
+    fn test_parse_key() {
+        for (s, expected) in [
+            ("Enter", Key::Named(NamedKey::Enter)),
+            ("enter", Key::Named(NamedKey::Enter)),
+            ("page up", Key::Named(NamedKey::PageUp)),
+            ("PAGEUP", Key::Named(NamedKey::PageUp)),
+            ("↩", Key::Named(NamedKey::Enter)),
+            ("K", Key::Character(SmolStr::new("k"))),
+            (",", Key::Character(SmolStr::new(","))),
+            ("<unknown>", Key::Unidentified(NativeKey::Unidentified)),
+            // Names from other platforms
+            ("Return", Key::Named(NamedKey::Enter)),
+            ("Forward Delete", Key::Named(NamedKey::Delete)),
+            ("Command", Key::Named(NamedKey::Super)),
+            ("Windows", Key::Named(NamedKey::Super)),
+            ("Right Option", Key::Named(NamedKey::AltGraph)),
+            ("Esc", Key::Named(NamedKey::Escape)),
+        ] {
+            assert_eq!(crate::parse_key(s), Some(expected), "{s:?}");
+        }
+        for s in ["", "KK", "Nonexistent", "f 1", "Page  Up"] {
+            assert_eq!(crate::parse_key(s), None, "{s:?}");
+        }
+
+        // Each platform's own names take priority.
+        let delete = Key::Named(NamedKey::Delete);
+        let backspace = Key::Named(NamedKey::Backspace);
+        assert_eq!(Platform::Linux.parse_key("Delete"), Some(delete.clone()));
+        assert_eq!(Platform::Windows.parse_key("Delete"), Some(delete));
+        assert_eq!(Platform::MacOS.parse_key("Delete"), Some(backspace));
+    }
+
+    #[test]
+    fn test_key_names_round_trip() {
+        for platform in Platform::ALL {
+            for named_key in NAMED_KEYS {
+                let name = platform.key_name(Key::Named(named_key));
+                let parsed = platform
+                    .parse_key(&name)
+                    .unwrap_or_else(|| panic!("failed to parse {name:?} on {platform:?}"));
+                assert_eq!(platform.key_name(parsed), name, "{named_key:?}");
+            }
+        }
+    }
+}
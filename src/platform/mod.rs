@@ -0,0 +1,230 @@
+//! Per-platform naming conventions, available regardless of the compile
+//! target.
+
+#[cfg(feature = "winit")]
+use winit::keyboard::{Key, NamedKey, NativeKey, PhysicalKey};
+
+use crate::ShortcutStyle;
+#[cfg(feature = "winit")]
+use crate::{
+    parse, unknown_physical_key_name, AsModifiersState, KeySequence, Shortcut, ShortcutKey,
+};
+
+mod linux;
+pub(crate) mod macos;
+pub(crate) mod web;
+mod windows;
+
+/// Platform whose naming conventions are used to format keys and modifiers.
+///
+/// [`Platform::NATIVE`] is the platform that this crate was compiled for, and
+/// is used by the free functions in this crate. Other platforms can be used to
+/// produce names for any platform at runtime, such as when generating a
+/// shortcut cheat sheet for macOS on Linux.
+///
+/// Only the naming conventions are available for other platforms. Names of
+/// physical keys come from a hard-coded US QWERTY layout, since the user's
+/// actual keyboard layout can only be queried on the native platform using
+/// [`crate::Layout`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Platform {
+    /// Linux (X11 or Wayland).
+    Linux,
+    /// macOS.
+    MacOS,
+    /// Windows.
+    Windows,
+    /// Web browsers.
+    Web,
+}
+
+impl Platform {
+    /// All platforms.
+    pub const ALL: [Platform; 4] = [
+        Platform::Linux,
+        Platform::MacOS,
+        Platform::Windows,
+        Platform::Web,
+    ];
+
+    /// Platform that this crate was compiled for.
+    #[cfg(target_os = "linux")]
+    pub const NATIVE: Platform = Platform::Linux;
+    /// Platform that this crate was compiled for.
+    #[cfg(target_os = "macos")]
+    pub const NATIVE: Platform = Platform::MacOS;
+    /// Platform that this crate was compiled for.
+    #[cfg(windows)]
+    pub const NATIVE: Platform = Platform::Windows;
+    /// Platform that this crate was compiled for.
+    #[cfg(target_arch = "wasm32")]
+    pub const NATIVE: Platform = Platform::Web;
+
+    /// Returns the platform's conventional modifiers order, represented as an
+    /// ASCII string containing the characters `csam` for `CTRL`, `SHIFT`,
+    /// `ALT`, and `META`/`LOGO` respectively in some order.
+    pub const fn modifiers_order(self) -> &'static str {
+        match self {
+            Platform::Linux => linux::MODIFIERS_ORDER,
+            Platform::MacOS => macos::MODIFIERS_ORDER,
+            Platform::Windows => windows::MODIFIERS_ORDER,
+            Platform::Web => web::MODIFIERS_ORDER,
+        }
+    }
+
+    /// Returns the platform's conventional name for the <key>Alt</key>
+    /// modifier.
+    pub const fn alt_str(self) -> &'static str {
+        match self {
+            Platform::Linux => linux::ALT_STR,
+            Platform::MacOS => macos::ALT_STR,
+            Platform::Windows => windows::ALT_STR,
+            Platform::Web => web::ALT_STR,
+        }
+    }
+
+    /// Returns the platform's conventional name for the logo modifier.
+    pub const fn logo_str(self) -> &'static str {
+        match self {
+            Platform::Linux => linux::LOGO_STR,
+            Platform::MacOS => macos::LOGO_STR,
+            Platform::Windows => windows::LOGO_STR,
+            Platform::Web => web::LOGO_STR,
+        }
+    }
+
+    /// Returns the platform's conventional style for formatting shortcuts.
+    pub const fn shortcut_style(self) -> ShortcutStyle {
+        match self {
+            Platform::Linux => ShortcutStyle::LINUX,
+            Platform::MacOS => ShortcutStyle::MACOS,
+            Platform::Windows => ShortcutStyle::WINDOWS,
+            Platform::Web => ShortcutStyle::WEB,
+        }
+    }
+}
+
+#[cfg(feature = "winit")]
+impl Platform {
+    /// Returns the platform-specific name for a named key, if it differs from
+    /// the `Debug` name of the key. For example, [`NamedKey::Super`] is called
+    /// "Windows" on Windows and "Command" on macOS.
+    pub fn os_specific_key_name(self, key: NamedKey) -> Option<&'static str> {
+        match self {
+            Platform::Linux => linux::os_specific_key_name(key),
+            Platform::MacOS => macos::os_specific_key_name(key),
+            Platform::Windows => windows::os_specific_key_name(key),
+            Platform::Web => web::os_specific_key_name(key),
+        }
+    }
+
+    /// Returns the hard-coded name for a physical key on a US QWERTY layout,
+    /// if there is one.
+    ///
+    /// macOS has its own names for some keys, such as "Return" and "Option".
+    /// All other platforms use the same names.
+    pub fn hardcoded_physical_key_name(self, physical_key: PhysicalKey) -> Option<&'static str> {
+        match self {
+            Platform::MacOS => macos::hardcoded_physical_key_name(physical_key),
+            Platform::Linux | Platform::Windows | Platform::Web => {
+                web::hardcoded_physical_key_name(physical_key)
+            }
+        }
+    }
+
+    /// Returns a human-friendly name for a physical key on a US QWERTY
+    /// layout, falling back to the `Debug` name of the key.
+    ///
+    /// For the native platform, this is the same as
+    /// [`crate::fallback_physical_key_name()`].
+    pub fn physical_key_name(self, physical_key: PhysicalKey) -> String {
+        match self.hardcoded_physical_key_name(physical_key) {
+            Some(name) => name.to_string(),
+            None => unknown_physical_key_name(physical_key),
+        }
+    }
+
+    /// Returns a human-friendly name for a virtual key.
+    ///
+    /// For the native platform, this is the same as [`crate::key_name()`].
+    pub fn key_name(self, key: Key) -> String {
+        match key {
+            Key::Named(named_key) => match self.os_specific_key_name(named_key) {
+                Some(name) => name.to_string(),
+                None => format!("{named_key:?}"),
+            },
+            Key::Character(c) => c.to_ascii_uppercase(),
+            Key::Unidentified(native_key) => match native_key {
+                NativeKey::Unidentified => "<unknown>".to_string(),
+                NativeKey::Android(sc) => format!("SC{sc}"),
+                NativeKey::MacOS(sc) => format!("SC{sc}"),
+                NativeKey::Windows(sc) => format!("SC{sc}"),
+                NativeKey::Xkb(sc) => format!("SC{sc}"),
+                NativeKey::Web(smol_str) => format!("{smol_str}"),
+            },
+            Key::Dead(None) => "<unknown>".to_string(),
+            Key::Dead(Some(c)) => c.into(),
+        }
+    }
+
+    /// Parses a virtual key name produced by [`Platform::key_name()`] back into
+    /// a virtual key, or returns `None` if it is not a valid key name.
+    ///
+    /// This accepts the same names as [`crate::parse_key()`], but names that
+    /// differ in meaning between platforms are interpreted using this
+    /// platform's conventions. For example, "Delete" is
+    /// [`NamedKey::Backspace`] on macOS and [`NamedKey::Delete`] everywhere
+    /// else.
+    ///
+    /// For the native platform, this is the same as [`crate::parse_key()`].
+    pub fn parse_key(self, s: &str) -> Option<Key> {
+        parse::parse_key(self, s)
+    }
+
+    /// Returns the names of the held modifiers using the platform's
+    /// conventional names and ordering, such as `["Ctrl", "Shift"]`.
+    pub fn modifier_names(self, mods: impl AsModifiersState) -> Vec<&'static str> {
+        self.shortcut_style().modifier_names(mods)
+    }
+
+    /// Returns a string representing modifiers using the platform's
+    /// conventional names and ordering, such as "Ctrl + Shift + ".
+    pub fn modifiers_prefix_string(self, mods: impl AsModifiersState) -> String {
+        self.shortcut_style().modifiers_prefix_string(mods)
+    }
+
+    /// Returns a human-friendly name for a shortcut using the platform's
+    /// conventions.
+    pub fn shortcut_name(self, shortcut: &Shortcut) -> String {
+        self.shortcut_name_with_style(shortcut, &self.shortcut_style())
+    }
+
+    /// Returns a human-friendly name for a shortcut in a particular style,
+    /// using the platform's names for keys.
+    pub fn shortcut_name_with_style(self, shortcut: &Shortcut, style: &ShortcutStyle) -> String {
+        style.style_shortcut_name(shortcut, self, |key| self.shortcut_key_name(key))
+    }
+
+    /// Returns a human-friendly name for a key sequence using the platform's
+    /// conventions.
+    pub fn key_sequence_name(self, sequence: &KeySequence) -> String {
+        self.key_sequence_name_with_style(sequence, &self.shortcut_style())
+    }
+
+    /// Returns a human-friendly name for a key sequence in a particular style,
+    /// using the platform's names for keys.
+    pub fn key_sequence_name_with_style(
+        self,
+        sequence: &KeySequence,
+        style: &ShortcutStyle,
+    ) -> String {
+        style.style_key_sequence_name(sequence, self, |key| self.shortcut_key_name(key))
+    }
+
+    fn shortcut_key_name(self, key: &ShortcutKey) -> String {
+        match key {
+            ShortcutKey::Physical(physical_key) => self.physical_key_name(*physical_key),
+            ShortcutKey::Logical(key) => self.key_name(key.clone()),
+        }
+    }
+}
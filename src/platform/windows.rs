@@ -0,0 +1,19 @@
+#[cfg(feature = "winit")]
+use winit::keyboard::NamedKey;
+
+pub const ALT_STR: &str = "Alt";
+pub const LOGO_STR: &str = "Win";
+pub const MODIFIERS_ORDER: &str = "csam"; // Ctrl + Shift + Alt + Meta
+
+#[cfg(feature = "winit")]
+pub fn os_specific_key_name(key: NamedKey) -> Option<&'static str> {
+    match key {
+        NamedKey::AltGraph => Some("AltGr"),
+        NamedKey::ArrowDown => Some("Down"),
+        NamedKey::ArrowLeft => Some("Left"),
+        NamedKey::ArrowRight => Some("Right"),
+        NamedKey::ArrowUp => Some("Up"),
+        NamedKey::Super => Some("Windows"),
+        _ => None,
+    }
+}
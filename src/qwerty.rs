@@ -0,0 +1,75 @@
+//! Reverse lookup for the hard-coded US QWERTY key name tables.
+
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::Level;
+
+/// Keys that produce a letter on the US QWERTY layout.
+const LETTER_KEYS: [KeyCode; 26] = {
+    use KeyCode::*;
+    [
+        KeyA, KeyB, KeyC, KeyD, KeyE, KeyF, KeyG, KeyH, KeyI, KeyJ, KeyK, KeyL, KeyM, KeyN, KeyO,
+        KeyP, KeyQ, KeyR, KeyS, KeyT, KeyU, KeyV, KeyW, KeyX, KeyY, KeyZ,
+    ]
+};
+
+/// Keys that produce a digit or symbol on the US QWERTY layout, along with the
+/// symbol they produce when <key>Shift</key> is held.
+const SHIFTED_SYMBOLS: [(KeyCode, char); 21] = {
+    use KeyCode::*;
+    [
+        (Backquote, '~'),
+        (Digit1, '!'),
+        (Digit2, '@'),
+        (Digit3, '#'),
+        (Digit4, '$'),
+        (Digit5, '%'),
+        (Digit6, '^'),
+        (Digit7, '&'),
+        (Digit8, '*'),
+        (Digit9, '('),
+        (Digit0, ')'),
+        (Minus, '_'),
+        (Equal, '+'),
+        (BracketLeft, '{'),
+        (BracketRight, '}'),
+        (Backslash, '|'),
+        (Semicolon, ':'),
+        (Quote, '"'),
+        (Comma, '<'),
+        (Period, '>'),
+        (Slash, '?'),
+    ]
+};
+
+/// Returns the physical key and level that produce a character on the US
+/// QWERTY layout, using `name_table` for the names of unshifted keys.
+pub fn physical_key_for_char(
+    name_table: fn(PhysicalKey) -> Option<&'static str>,
+    c: char,
+) -> Option<(PhysicalKey, Level)> {
+    // Letters are named using uppercase, but uppercase letters require Shift.
+    let level = if c.is_ascii_uppercase() {
+        Level::Shift
+    } else {
+        Level::Base
+    };
+    let mut buffer = [0; 4];
+    let name = &*c.to_ascii_uppercase().encode_utf8(&mut buffer);
+
+    let unshifted_keys = SHIFTED_SYMBOLS
+        .iter()
+        .map(|&(key, _)| key)
+        .chain(LETTER_KEYS);
+    for key_code in unshifted_keys {
+        let physical_key = PhysicalKey::Code(key_code);
+        if name_table(physical_key) == Some(name) {
+            return Some((physical_key, level));
+        }
+    }
+
+    SHIFTED_SYMBOLS
+        .iter()
+        .find(|&&(_, shifted)| shifted == c)
+        .map(|&(key_code, _)| (PhysicalKey::Code(key_code), Level::Shift))
+}
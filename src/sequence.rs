@@ -0,0 +1,390 @@
+//! Multi-stroke key sequences, such as <key>Ctrl</key>+<key>K</key>
+//! <key>Ctrl</key>+<key>C</key>.
+
+use std::fmt;
+use std::str::FromStr;
+
+use winit::event::{ElementState, KeyEvent};
+use winit::keyboard::{Key, NamedKey};
+
+use crate::shortcut::{leading_whitespace_len, parse_physical_or_logical_key, parse_shortcut};
+use crate::{
+    parse_key, parse_physical_key, Layout, Shortcut, ShortcutKey, ShortcutParseError,
+    ShortcutStyle, SidedModifiers,
+};
+
+/// Sequence of shortcuts that must be pressed one after another, such as
+/// <key>Ctrl</key>+<key>K</key> <key>Ctrl</key>+<key>C</key> in VS Code or
+/// <key>Ctrl</key>+<key>X</key> <key>Ctrl</key>+<key>F</key> in Emacs.
+///
+/// The [`Display`](fmt::Display) implementation formats each shortcut using
+/// the OS's conventions and separates them using commas, such as "Ctrl + K,
+/// Ctrl + C".
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct KeySequence {
+    strokes: Vec<Shortcut>,
+}
+
+impl From<Shortcut> for KeySequence {
+    fn from(shortcut: Shortcut) -> Self {
+        KeySequence {
+            strokes: vec![shortcut],
+        }
+    }
+}
+impl From<Vec<Shortcut>> for KeySequence {
+    fn from(strokes: Vec<Shortcut>) -> Self {
+        KeySequence { strokes }
+    }
+}
+impl FromIterator<Shortcut> for KeySequence {
+    fn from_iter<T: IntoIterator<Item = Shortcut>>(iter: T) -> Self {
+        KeySequence {
+            strokes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl KeySequence {
+    /// Constructs a key sequence from a list of shortcuts.
+    pub fn new(strokes: Vec<Shortcut>) -> Self {
+        KeySequence { strokes }
+    }
+
+    /// Returns the shortcuts in the sequence.
+    pub fn strokes(&self) -> &[Shortcut] {
+        &self.strokes
+    }
+
+    /// Returns the number of shortcuts in the sequence.
+    pub fn len(&self) -> usize {
+        self.strokes.len()
+    }
+
+    /// Returns whether the sequence contains no shortcuts.
+    pub fn is_empty(&self) -> bool {
+        self.strokes.is_empty()
+    }
+
+    /// Parses a key sequence, interpreting each key as a physical key using
+    /// the default layout.
+    ///
+    /// See [`KeySequence::from_str()`] for the accepted syntax.
+    pub fn parse_physical(s: &str) -> Result<Self, ShortcutParseError> {
+        parse_key_sequence(s, |key| parse_physical_key(key).map(ShortcutKey::Physical))
+    }
+
+    /// Parses a key sequence, interpreting each key as a logical key.
+    ///
+    /// See [`KeySequence::from_str()`] for the accepted syntax.
+    pub fn parse_logical(s: &str) -> Result<Self, ShortcutParseError> {
+        parse_key_sequence(s, |key| parse_key(key).map(ShortcutKey::Logical))
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = ShortcutParseError;
+
+    /// Parses a key sequence such as "Ctrl + K, Ctrl + C" or "Ctrl+K Ctrl+C",
+    /// interpreting each key as a physical key using the default layout if
+    /// possible and as a logical key otherwise.
+    ///
+    /// Shortcuts may be separated by commas or whitespace, and each shortcut
+    /// accepts the same syntax as [`Shortcut::from_str()`]. Because shortcuts
+    /// may themselves contain whitespace, each one is taken to be the longest
+    /// prefix of the remaining input that is a valid shortcut.
+    ///
+    /// A comma is only a separator if it directly follows a shortcut and
+    /// another shortcut follows it. Any other comma, including a leading or
+    /// trailing comma, is the comma key. For example, ", Ctrl + K", "Ctrl + K,
+    /// ,", and "C-k , C-c" each contain the comma key.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_key_sequence(s, parse_physical_or_logical_key)
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", ShortcutStyle::NATIVE.key_sequence_name(self))
+    }
+}
+
+impl Layout {
+    /// Returns a human-friendly name for a key sequence, using this layout for
+    /// the names of physical keys.
+    pub fn key_sequence_name(&self, sequence: &KeySequence) -> String {
+        self.key_sequence_name_with_style(sequence, &ShortcutStyle::NATIVE)
+    }
+
+    /// Parses a key sequence, interpreting each key as a physical key using
+    /// this layout if possible and as a logical key otherwise.
+    ///
+    /// See [`KeySequence::from_str()`] for the accepted syntax.
+    pub fn parse_key_sequence(&self, s: &str) -> Result<KeySequence, ShortcutParseError> {
+        parse_key_sequence(s, |key| {
+            self.parse_physical_key(key)
+                .map(ShortcutKey::Physical)
+                .or_else(|| parse_key(key).map(ShortcutKey::Logical))
+        })
+    }
+}
+
+fn parse_key_sequence(
+    s: &str,
+    parse_key: impl Fn(&str) -> Option<ShortcutKey>,
+) -> Result<KeySequence, ShortcutParseError> {
+    let is_delimiter = |c: char| c == ',' || c.is_whitespace();
+
+    let mut strokes = vec![];
+    let mut pos = 0;
+    loop {
+        // Skip at most one comma directly after the previous shortcut, as long
+        // as another shortcut follows it. Any other comma is the comma key.
+        if !strokes.is_empty() && s[pos..].starts_with(',') {
+            let next = pos + 1 + leading_whitespace_len(&s[pos + 1..]);
+            if next < s.len() {
+                pos = next;
+            }
+        }
+        pos += leading_whitespace_len(&s[pos..]);
+        if pos == s.len() && !strokes.is_empty() {
+            return Ok(KeySequence { strokes });
+        }
+
+        // Try each possible end of the shortcut, longest first.
+        let rest = &s[pos..];
+        let mut ends: Vec<usize> = rest
+            .char_indices()
+            .filter(|&(i, c)| i > 0 && is_delimiter(c))
+            .map(|(i, _)| i)
+            .collect();
+        ends.push(rest.len());
+        let mut longest_error = None;
+        for &end in ends.iter().rev() {
+            match parse_shortcut(&rest[..end], &parse_key) {
+                Ok(shortcut) => {
+                    strokes.push(shortcut);
+                    pos += rest[..end].trim_end().len();
+                    longest_error = None;
+                    break;
+                }
+                Err(e) => {
+                    longest_error.get_or_insert(e);
+                }
+            }
+        }
+        if let Some(mut e) = longest_error {
+            e.span = e.span.start + pos..e.span.end + pos;
+            return Err(e);
+        }
+    }
+}
+
+/// Result of feeding a key event to a [`KeySequenceMatcher`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SequenceMatch {
+    /// The key event matched the next shortcut in the sequence, but more
+    /// shortcuts are needed to complete it.
+    Partial,
+    /// The key event matched the last shortcut in the sequence. The matcher
+    /// has been reset.
+    Complete,
+    /// The key event did not match the next shortcut in the sequence. The
+    /// matcher has been reset.
+    Failed,
+}
+
+/// Matches winit key events against a [`KeySequence`].
+///
+/// Call [`KeySequenceMatcher::feed()`] with each key event and the modifiers
+/// held at the time, as winit's [`Modifiers`](winit::event::Modifiers) if any
+/// stroke requires a modifier on a particular side of the keyboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequenceMatcher {
+    sequence: KeySequence,
+    progress: usize,
+}
+
+impl KeySequenceMatcher {
+    /// Constructs a matcher for a key sequence.
+    pub fn new(sequence: KeySequence) -> Self {
+        KeySequenceMatcher {
+            sequence,
+            progress: 0,
+        }
+    }
+
+    /// Returns the key sequence being matched.
+    pub fn sequence(&self) -> &KeySequence {
+        &self.sequence
+    }
+
+    /// Returns the number of shortcuts in the sequence that have been matched
+    /// so far.
+    pub fn progress(&self) -> usize {
+        self.progress
+    }
+
+    /// Resets the matcher to the start of the sequence.
+    pub fn reset(&mut self) {
+        self.progress = 0;
+    }
+
+    /// Feeds a key event to the matcher.
+    ///
+    /// Returns `None` if the event is ignored, which happens for key releases,
+    /// key repeats, and presses of modifier keys that are not themselves the
+    /// next key in the sequence. Otherwise returns whether the sequence is
+    /// partially matched, completely matched, or failed to match.
+    pub fn feed(
+        &mut self,
+        event: &KeyEvent,
+        mods: impl Into<SidedModifiers>,
+    ) -> Option<SequenceMatch> {
+        if event.state != ElementState::Pressed || event.repeat {
+            return None;
+        }
+        let Some(expected) = self.sequence.strokes.get(self.progress) else {
+            return Some(SequenceMatch::Failed);
+        };
+        if expected.matches(event, mods) {
+            self.progress += 1;
+            if self.progress == self.sequence.strokes.len() {
+                self.progress = 0;
+                Some(SequenceMatch::Complete)
+            } else {
+                Some(SequenceMatch::Partial)
+            }
+        } else if is_modifier_key(&event.logical_key) {
+            None
+        } else {
+            self.progress = 0;
+            Some(SequenceMatch::Failed)
+        }
+    }
+}
+
+fn is_modifier_key(key: &Key) -> bool {
+    matches!(
+        key,
+        Key::Named(
+            NamedKey::Alt
+                | NamedKey::AltGraph
+                | NamedKey::Control
+                | NamedKey::Fn
+                | NamedKey::Hyper
+                | NamedKey::Meta
+                | NamedKey::Shift
+                | NamedKey::Super
+                | NamedKey::Symbol
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::keyboard::ModifiersState;
+
+    use super::*;
+    use crate::shortcut::tests::character;
+    use crate::ShortcutParseErrorKind;
+
+    fn shortcut(mods: ModifiersState, c: &str) -> Shortcut {
+        Shortcut::new(mods, character(c))
+    }
+
+    fn parse(s: &str) -> Result<Vec<Shortcut>, ShortcutParseError> {
+        KeySequence::parse_logical(s).map(|sequence| sequence.strokes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_key_sequence() {
+        let none = ModifiersState::empty();
+        let ctrl = ModifiersState::CONTROL;
+        let ctrl_k = shortcut(ctrl, "k");
+        let ctrl_c = shortcut(ctrl, "c");
+        let comma = shortcut(none, ",");
+        for (s, expected) in [
+            ("Ctrl + K", vec![ctrl_k.clone()]),
+            ("Ctrl + K, Ctrl + C", vec![ctrl_k.clone(), ctrl_c.clone()]),
+            ("Ctrl+K Ctrl+C", vec![ctrl_k.clone(), ctrl_c.clone()]),
+            ("Ctrl+K,Ctrl+C", vec![ctrl_k.clone(), ctrl_c.clone()]),
+            ("  C-k   C-c  ", vec![ctrl_k.clone(), ctrl_c.clone()]),
+            ("⌃K, ⌃C", vec![ctrl_k.clone(), ctrl_c.clone()]),
+            ("C-f 1", vec![shortcut(ctrl, "f"), shortcut(none, "1")]),
+            ("F 1", vec![shortcut(none, "f"), shortcut(none, "1")]),
+            (
+                "C-k , C-c",
+                vec![ctrl_k.clone(), comma.clone(), ctrl_c.clone()],
+            ),
+            ("Ctrl + K, ,", vec![ctrl_k.clone(), comma.clone()]),
+            ("Ctrl + K,", vec![ctrl_k.clone(), comma.clone()]),
+            ("C-k ,", vec![ctrl_k.clone(), comma.clone()]),
+            (", Ctrl + K", vec![comma.clone(), ctrl_k.clone()]),
+            (
+                ", , Ctrl + K",
+                vec![comma.clone(), comma.clone(), ctrl_k.clone()],
+            ),
+            (
+                "Ctrl + K, ,, Ctrl + C",
+                vec![ctrl_k.clone(), comma.clone(), ctrl_c.clone()],
+            ),
+            (
+                "Ctrl + K, , , Ctrl + C",
+                vec![ctrl_k.clone(), comma.clone(), comma, ctrl_c],
+            ),
+            // The longest valid prefix is taken, so "Ctrl + Page Up" is one
+            // shortcut rather than "Ctrl + Page" (which is invalid) and "Up".
+            (
+                "Ctrl + Page Up K",
+                vec![Shortcut::new(ctrl, NamedKey::PageUp), shortcut(none, "k")],
+            ),
+        ] {
+            assert_eq!(parse(s), Ok(expected), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_key_sequence_errors() {
+        use ShortcutParseErrorKind::*;
+
+        let error = |s| {
+            let e = parse(s).unwrap_err();
+            (e.kind, e.span)
+        };
+        assert_eq!(error(""), (MissingKey, 0..0));
+        assert_eq!(error("   "), (MissingKey, 3..3));
+        assert_eq!(error("Ctrl + K, Ctrl + "), (MissingKey, 17..17));
+        assert_eq!(error("Ctrl + K, Foo + C"), (UnknownModifier, 10..13));
+        assert_eq!(error("Ctrl + K, Ctrl + Nonexistent"), (UnknownKey, 17..28));
+    }
+
+    #[test]
+    fn test_key_sequence_names_round_trip() {
+        let none = ModifiersState::empty();
+        let ctrl = ModifiersState::CONTROL;
+        let sequences = [
+            vec![
+                shortcut(ctrl, "k"),
+                shortcut(none, ","),
+                shortcut(ModifiersState::SHIFT | ModifiersState::ALT, "-"),
+                Shortcut::new(none, NamedKey::Enter),
+                shortcut(none, ","),
+            ],
+            // These must not be mistaken for `F1`, as in "C-f 1" and "F 1".
+            vec![shortcut(ctrl, "f"), shortcut(none, "1")],
+            vec![shortcut(none, "f"), shortcut(none, "1")],
+        ]
+        .map(KeySequence::new);
+        for style in ShortcutStyle::PRESETS {
+            for sequence in &sequences {
+                let name = style.key_sequence_name(sequence);
+                assert_eq!(
+                    KeySequence::parse_logical(&name),
+                    Ok(sequence.clone()),
+                    "{name:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,642 @@
+//! Keyboard shortcuts consisting of modifiers and a key.
+
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use winit::event::KeyEvent;
+use winit::keyboard::{Key, KeyCode, ModifiersState, NamedKey, PhysicalKey};
+#[cfg(not(target_arch = "wasm32"))]
+use winit::platform::modifier_supplement::KeyEventExtModifierSupplement;
+
+use crate::{
+    key_name, parse_key, parse_physical_key, physical_key_name, sided_modifiers_prefix_string,
+    Layout, ShortcutStyle, SidedModifiers,
+};
+
+/// Characters that may separate modifiers from each other and from the key.
+const SEPARATORS: [char; 2] = ['+', '-'];
+
+/// Names accepted for each modifier. Matching is case-insensitive.
+const MODIFIER_ALIASES: &[(&str, ModifiersState)] = &[
+    ("shift", ModifiersState::SHIFT),
+    ("ctrl", ModifiersState::CONTROL),
+    ("control", ModifiersState::CONTROL),
+    ("ctl", ModifiersState::CONTROL),
+    ("alt", ModifiersState::ALT),
+    ("option", ModifiersState::ALT),
+    ("opt", ModifiersState::ALT),
+    ("super", ModifiersState::SUPER),
+    ("win", ModifiersState::SUPER),
+    ("windows", ModifiersState::SUPER),
+    ("cmd", ModifiersState::SUPER),
+    ("command", ModifiersState::SUPER),
+    ("meta", ModifiersState::SUPER),
+    ("logo", ModifiersState::SUPER),
+];
+
+/// Sides of the keyboard that may precede a modifier name, as in "Right
+/// Control". Matching is case-insensitive.
+const MODIFIER_SIDES: [(&str, Side); 2] = [("left", Side::Left), ("right", Side::Right)];
+
+/// Side of the keyboard on which a modifier must be held.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// Emacs-style abbreviations for each modifier. Matching is case-sensitive.
+const MODIFIER_ABBREVIATIONS: &[(&str, ModifiersState)] = &[
+    ("S", ModifiersState::SHIFT),
+    ("C", ModifiersState::CONTROL),
+    ("M", ModifiersState::ALT),
+    ("s", ModifiersState::SUPER),
+];
+
+/// macOS-style symbols for each modifier, which do not require a separator.
+const MODIFIER_SYMBOLS: &[(&str, ModifiersState)] = &[
+    ("⇧", ModifiersState::SHIFT),
+    ("⌃", ModifiersState::CONTROL),
+    ("⌥", ModifiersState::ALT),
+    ("⌘", ModifiersState::SUPER),
+];
+
+/// Key of a [`Shortcut`], which may be either a physical key or a logical key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ShortcutKey {
+    /// Physical key, which is named according to the user's keyboard layout.
+    Physical(PhysicalKey),
+    /// Logical key, which is named according to the symbol it produces.
+    Logical(Key),
+}
+
+impl From<PhysicalKey> for ShortcutKey {
+    fn from(physical_key: PhysicalKey) -> Self {
+        ShortcutKey::Physical(physical_key)
+    }
+}
+impl From<KeyCode> for ShortcutKey {
+    fn from(key_code: KeyCode) -> Self {
+        ShortcutKey::Physical(PhysicalKey::Code(key_code))
+    }
+}
+impl From<Key> for ShortcutKey {
+    fn from(key: Key) -> Self {
+        ShortcutKey::Logical(key)
+    }
+}
+impl From<NamedKey> for ShortcutKey {
+    fn from(named_key: NamedKey) -> Self {
+        ShortcutKey::Logical(Key::Named(named_key))
+    }
+}
+
+impl fmt::Display for ShortcutKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortcutKey::Physical(physical_key) => {
+                write!(f, "{}", physical_key_name(*physical_key))
+            }
+            ShortcutKey::Logical(key) => write!(f, "{}", key_name(key.clone())),
+        }
+    }
+}
+
+/// Keyboard shortcut consisting of zero or more modifiers and a key, such as
+/// <key>Ctrl</key>+<key>Shift</key>+<key>K</key>.
+///
+/// The [`Display`](fmt::Display) implementation uses the OS's conventional
+/// modifier names and ordering (see [`sided_modifiers_prefix_string()`])
+/// followed by the key name, such as "Ctrl + Shift + K" on Windows or
+/// "Ctrl + Option + Cmd + K" on macOS.
+///
+/// A modifier may be required to be held on a particular side of the keyboard,
+/// as in <key>Right Ctrl</key>+<key>K</key>, by including it in
+/// [`SidedModifiers::left`] or [`SidedModifiers::right`] as well as
+/// [`SidedModifiers::state`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    /// Modifiers that must be held.
+    pub mods: SidedModifiers,
+    /// Key that must be pressed.
+    pub key: ShortcutKey,
+}
+
+impl Shortcut {
+    /// Constructs a shortcut from modifiers and a key.
+    pub fn new(mods: impl Into<SidedModifiers>, key: impl Into<ShortcutKey>) -> Self {
+        Shortcut {
+            mods: mods.into(),
+            key: key.into(),
+        }
+    }
+
+    /// Constructs a shortcut from a key event and the modifiers held at the
+    /// time, using the physical key.
+    pub fn from_physical_key_event(event: &KeyEvent, mods: ModifiersState) -> Self {
+        Self::new(mods, event.physical_key)
+    }
+
+    /// Constructs a shortcut from a key event and the modifiers held at the
+    /// time, using the logical key.
+    ///
+    /// Where supported, the logical key is taken without modifiers applied, so
+    /// that <key>Shift</key>+<key>K</key> produces the key "k" with the
+    /// <key>Shift</key> modifier rather than the key "K".
+    pub fn from_logical_key_event(event: &KeyEvent, mods: ModifiersState) -> Self {
+        Self::new(mods, logical_key_without_modifiers(event))
+    }
+
+    /// Returns whether a key event with the given modifiers held triggers this
+    /// shortcut. This does not check whether the key was pressed or released.
+    ///
+    /// Letters in logical keys are compared case-insensitively. Modifiers that
+    /// this shortcut requires on a particular side of the keyboard must be held
+    /// on that side, so a shortcut with side-specific modifiers never matches
+    /// a [`ModifiersState`], which has no side information.
+    pub fn matches(&self, event: &KeyEvent, mods: impl Into<SidedModifiers>) -> bool {
+        let mods = mods.into();
+        if mods.state != self.mods.state
+            || !mods.left.contains(self.mods.left)
+            || !mods.right.contains(self.mods.right)
+        {
+            return false;
+        }
+        match &self.key {
+            ShortcutKey::Physical(physical_key) => event.physical_key == *physical_key,
+            ShortcutKey::Logical(key) => match (key, logical_key_without_modifiers(event)) {
+                (Key::Character(a), Key::Character(b)) => a.eq_ignore_ascii_case(&b),
+                (a, b) => *a == b,
+            },
+        }
+    }
+
+    /// Returns a string representing the modifiers of this shortcut using the
+    /// OS's conventional names and ordering, such as "Ctrl + Shift + ".
+    ///
+    /// Side-specific modifiers are named as in
+    /// [`sided_modifiers_prefix_string()`].
+    pub fn modifiers_prefix_string(&self) -> String {
+        sided_modifiers_prefix_string(self.mods)
+    }
+}
+
+impl Shortcut {
+    /// Parses a shortcut such as "Ctrl + Shift + K", interpreting the key as a
+    /// physical key using the default layout.
+    ///
+    /// See [`Shortcut::from_str()`] for the accepted syntax.
+    pub fn parse_physical(s: &str) -> Result<Self, ShortcutParseError> {
+        parse_shortcut(s, |key| parse_physical_key(key).map(ShortcutKey::Physical))
+    }
+
+    /// Parses a shortcut such as "Ctrl + Shift + K", interpreting the key as a
+    /// logical key.
+    ///
+    /// See [`Shortcut::from_str()`] for the accepted syntax.
+    pub fn parse_logical(s: &str) -> Result<Self, ShortcutParseError> {
+        parse_shortcut(s, |key| parse_key(key).map(ShortcutKey::Logical))
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ShortcutParseError;
+
+    /// Parses a shortcut such as "Ctrl + Shift + K", interpreting the key as a
+    /// physical key using the default layout if possible and as a logical key
+    /// otherwise.
+    ///
+    /// This accepts the output of the [`Display`](fmt::Display)
+    /// implementation on every platform. Modifiers may be written in any order
+    /// using any of their common names (such as "Control", "Option", "Win",
+    /// "Super", "Cmd", or "Meta"), case-insensitively, and may be separated by
+    /// either `+` or `-` with optional whitespace. The separator itself may be
+    /// used as the key, as in "Ctrl + +" or "Ctrl--". Modifier names may be
+    /// prefixed with "Left" or "Right", as in the output of
+    /// [`sided_modifiers_prefix_string()`], in which case the modifier must be
+    /// held on that side of the keyboard.
+    ///
+    /// The output of every [`ShortcutStyle`] preset is also accepted, including
+    /// Emacs-style abbreviations such as "C-M-k" (where "M" is
+    /// <key>Alt</key> and "s" is the logo key) and macOS-style symbols such as
+    /// "⌃⌥⇧⌘K", which do not require a separator.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_shortcut(s, parse_physical_or_logical_key)
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", ShortcutStyle::NATIVE.shortcut_name(self))
+    }
+}
+
+impl Layout {
+    /// Returns a human-friendly name for a shortcut, using this layout for the
+    /// name of a physical key.
+    pub fn shortcut_name(&self, shortcut: &Shortcut) -> String {
+        self.shortcut_name_with_style(shortcut, &ShortcutStyle::NATIVE)
+    }
+
+    /// Parses a shortcut such as "Ctrl + Shift + K", interpreting the key as a
+    /// physical key using this layout if possible and as a logical key
+    /// otherwise.
+    ///
+    /// See [`Shortcut::from_str()`] for the accepted syntax.
+    pub fn parse_shortcut(&self, s: &str) -> Result<Shortcut, ShortcutParseError> {
+        parse_shortcut(s, |key| {
+            self.parse_physical_key(key)
+                .map(ShortcutKey::Physical)
+                .or_else(|| parse_key(key).map(ShortcutKey::Logical))
+        })
+    }
+}
+
+/// Error returned when a shortcut string cannot be parsed.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{kind} at {}..{}", span.start, span.end)]
+pub struct ShortcutParseError {
+    /// What went wrong.
+    pub kind: ShortcutParseErrorKind,
+    /// Byte range of the offending token in the input string.
+    pub span: Range<usize>,
+}
+
+/// Kind of [`ShortcutParseError`].
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ShortcutParseErrorKind {
+    /// The shortcut has no key, such as "Ctrl + " or "".
+    #[error("missing key")]
+    MissingKey,
+    /// The key name is not recognized.
+    #[error("unknown key")]
+    UnknownKey,
+    /// A token followed by a separator is not a modifier, such as "Hyper" in
+    /// "Hyper + K".
+    #[error("unknown modifier")]
+    UnknownModifier,
+    /// The same modifier appears more than once, such as "Ctrl + Control + K".
+    #[error("duplicate modifier")]
+    DuplicateModifier,
+}
+
+fn logical_key_without_modifiers(event: &KeyEvent) -> Key {
+    #[cfg(not(target_arch = "wasm32"))]
+    return event.key_without_modifiers();
+    #[cfg(target_arch = "wasm32")]
+    return event.logical_key.clone();
+}
+
+/// Parses a key name as a physical key using the default layout if possible
+/// and as a logical key otherwise.
+pub(crate) fn parse_physical_or_logical_key(s: &str) -> Option<ShortcutKey> {
+    parse_physical_key(s)
+        .map(ShortcutKey::Physical)
+        .or_else(|| parse_key(s).map(ShortcutKey::Logical))
+}
+
+pub(crate) fn parse_shortcut(
+    s: &str,
+    parse_key: impl Fn(&str) -> Option<ShortcutKey>,
+) -> Result<Shortcut, ShortcutParseError> {
+    let error = |kind, span| Err(ShortcutParseError { kind, span });
+
+    // Consume modifiers as long as each one is followed by a separator (or,
+    // for symbols, by anything at all). The rest of the string is the key.
+    let mut mods = SidedModifiers::default();
+    let mut pos = 0;
+    loop {
+        let start = pos + leading_whitespace_len(&s[pos..]);
+        let Some((modifier, len, is_symbol)) = modifier_prefix(&s[start..]) else {
+            break;
+        };
+        let end = start + len;
+        let sep_start = end + leading_whitespace_len(&s[end..]);
+        let sep = s[sep_start..]
+            .chars()
+            .next()
+            .filter(|c| SEPARATORS.contains(c));
+        let next_pos = match sep {
+            // Symbols do not require a separator, so a separator followed by
+            // nothing or by a comma is the key, as in "⌃-" or "⌥⇧-, ↩".
+            Some(sep) if is_symbol && is_key_after_symbol(&s[sep_start + sep.len_utf8()..]) => end,
+            Some(sep) => sep_start + sep.len_utf8(),
+            None if is_symbol && sep_start < s.len() => end,
+            None => break,
+        };
+        if mods.state.intersects(modifier.state) {
+            return error(ShortcutParseErrorKind::DuplicateModifier, start..end);
+        }
+        mods.state |= modifier.state;
+        mods.left |= modifier.left;
+        mods.right |= modifier.right;
+        pos = next_pos;
+    }
+
+    let key_start = pos + leading_whitespace_len(&s[pos..]);
+    let key_str = s[key_start..].trim_end();
+    let key_span = key_start..key_start + key_str.len();
+    if key_str.is_empty() {
+        return error(ShortcutParseErrorKind::MissingKey, key_span);
+    }
+    if let Some(key) = parse_key(key_str) {
+        return Ok(Shortcut { mods, key });
+    }
+
+    // If the key is unknown but looks like it is followed by a separator, then
+    // it was probably meant to be a modifier.
+    if let Some(sep_index) = key_str.find(SEPARATORS).filter(|&i| i > 0) {
+        let token = key_str[..sep_index].trim_end();
+        if !token.is_empty() {
+            return error(
+                ShortcutParseErrorKind::UnknownModifier,
+                key_start..key_start + token.len(),
+            );
+        }
+    }
+    error(ShortcutParseErrorKind::UnknownKey, key_span)
+}
+
+/// Returns whether a separator after a modifier symbol is the key, given the
+/// rest of the string after the separator.
+fn is_key_after_symbol(rest: &str) -> bool {
+    rest.trim().is_empty() || rest.starts_with(',')
+}
+
+pub(crate) fn leading_whitespace_len(s: &str) -> usize {
+    s.len() - s.trim_start().len()
+}
+
+/// Returns the modifier at the start of `s`, the length of its name, and
+/// whether it is a symbol, if there is one.
+///
+/// Names may be prefixed with a side, as in "Right Control", which is
+/// recorded in [`SidedModifiers::left`] or [`SidedModifiers::right`].
+fn modifier_prefix(s: &str) -> Option<(SidedModifiers, usize, bool)> {
+    if let Some((side, side_len)) = side_prefix(s) {
+        if let Some((modifier, len)) = modifier_alias_prefix(&s[side_len..]) {
+            let mut mods = SidedModifiers::from(modifier);
+            match side {
+                Side::Left => mods.left = modifier,
+                Side::Right => mods.right = modifier,
+            }
+            return Some((mods, side_len + len, false));
+        }
+    }
+    if let Some((modifier, len)) = modifier_alias_prefix(s) {
+        return Some((modifier.into(), len, false));
+    }
+    let is_whole_word = |len: usize| !s[len..].starts_with(char::is_alphanumeric);
+    let abbreviation = || {
+        MODIFIER_ABBREVIATIONS
+            .iter()
+            .find(|&&(abbreviation, _)| s.starts_with(abbreviation) && is_whole_word(1))
+    };
+    if let Some(&(name, modifier)) = abbreviation() {
+        return Some((modifier.into(), name.len(), false));
+    }
+    MODIFIER_SYMBOLS
+        .iter()
+        .find(|&&(symbol, _)| s.starts_with(symbol))
+        .map(|&(symbol, modifier)| (modifier.into(), symbol.len(), true))
+}
+
+/// Returns the modifier named by an alias at the start of `s` and the length
+/// of the alias, if there is one.
+fn modifier_alias_prefix(s: &str) -> Option<(ModifiersState, usize)> {
+    MODIFIER_ALIASES
+        .iter()
+        .find(|&&(alias, _)| starts_with_word_ignore_case(s, alias))
+        .map(|&(alias, modifier)| (modifier, alias.len()))
+}
+
+/// Returns the side named at the start of `s`, such as "Right ", and its
+/// length including the whitespace after it, if there is one.
+fn side_prefix(s: &str) -> Option<(Side, usize)> {
+    let &(name, side) = MODIFIER_SIDES
+        .iter()
+        .find(|&&(name, _)| starts_with_word_ignore_case(s, name))?;
+    let whitespace_len = leading_whitespace_len(&s[name.len()..]);
+    (whitespace_len > 0).then_some((side, name.len() + whitespace_len))
+}
+
+/// Returns whether `s` starts with `word`, ignoring case, followed by a
+/// non-alphanumeric character or the end of the string.
+fn starts_with_word_ignore_case(s: &str, word: &str) -> bool {
+    s.get(..word.len()).is_some_and(|prefix| {
+        prefix.eq_ignore_ascii_case(word) && !s[word.len()..].starts_with(char::is_alphanumeric)
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use winit::keyboard::SmolStr;
+
+    use super::*;
+    #[cfg(target_os = "linux")]
+    use crate::Backend;
+    use crate::Platform;
+
+    fn all_mods() -> impl Iterator<Item = ModifiersState> {
+        (0..16).map(|bits| {
+            let mut mods = ModifiersState::empty();
+            mods.set(ModifiersState::SHIFT, bits & 1 != 0);
+            mods.set(ModifiersState::CONTROL, bits & 2 != 0);
+            mods.set(ModifiersState::ALT, bits & 4 != 0);
+            mods.set(ModifiersState::SUPER, bits & 8 != 0);
+            mods
+        })
+    }
+
+    pub(crate) fn character(s: &str) -> ShortcutKey {
+        ShortcutKey::Logical(Key::Character(SmolStr::new(s)))
+    }
+
+    fn parse_error(s: &str) -> (ShortcutParseErrorKind, Range<usize>) {
+        let e = Shortcut::parse_logical(s).unwrap_err();
+        (e.kind, e.span)
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_physical_shortcut_names_round_trip() {
+        let layout = Layout::from_backend(Backend::Static).unwrap();
+        let keys = [
+            KeyCode::KeyK,
+            KeyCode::Digit1,
+            KeyCode::Minus,
+            KeyCode::Equal,
+            KeyCode::Comma,
+            KeyCode::Space,
+            KeyCode::Enter,
+            KeyCode::ArrowUp,
+            KeyCode::F5,
+        ];
+        for style in ShortcutStyle::PRESETS {
+            for mods in all_mods() {
+                for key_code in keys {
+                    let shortcut = Shortcut::new(mods, key_code);
+                    let name = layout.shortcut_name_with_style(&shortcut, &style);
+                    assert_eq!(layout.parse_shortcut(&name), Ok(shortcut), "{name:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_logical_shortcut_names_round_trip() {
+        let keys = [
+            character("k"),
+            character("+"),
+            character("-"),
+            character(","),
+            ShortcutKey::from(NamedKey::Enter),
+            ShortcutKey::from(NamedKey::ArrowUp),
+        ];
+        for style in ShortcutStyle::PRESETS {
+            for mods in all_mods() {
+                for key in &keys {
+                    let shortcut = Shortcut::new(mods, key.clone());
+                    let name = style.shortcut_name(&shortcut);
+                    assert_eq!(Shortcut::parse_logical(&name), Ok(shortcut), "{name:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_platform_shortcut_names_round_trip() {
+        // macOS calls `Backspace` "Delete", which means `Delete` everywhere
+        // else, so it is omitted.
+        let mut keys = vec![character("k")];
+        keys.extend(
+            [
+                NamedKey::Enter,
+                NamedKey::Escape,
+                NamedKey::Delete,
+                NamedKey::ArrowUp,
+                NamedKey::Alt,
+                NamedKey::AltGraph,
+                NamedKey::Super,
+            ]
+            .map(ShortcutKey::from),
+        );
+        for platform in Platform::ALL {
+            for mods in all_mods() {
+                for key in &keys {
+                    let shortcut = Shortcut::new(mods, key.clone());
+                    let name = platform.shortcut_name(&shortcut);
+                    assert_eq!(Shortcut::parse_logical(&name), Ok(shortcut), "{name:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_shortcut() {
+        let ctrl = ModifiersState::CONTROL;
+        let all = ModifiersState::all();
+        for (s, mods, key) in [
+            ("K", ModifiersState::empty(), character("k")),
+            ("Ctrl+K", ctrl, character("k")),
+            ("  ctrl  -  k  ", ctrl, character("k")),
+            ("Ctrl--", ctrl, character("-")),
+            ("Ctrl + +", ctrl, character("+")),
+            ("Ctrl + -", ctrl, character("-")),
+            ("Shift+Alt+Ctrl+Meta+K", all, character("k")),
+            ("⌃⌥⇧⌘K", all, character("k")),
+            ("⌃ + K", ctrl, character("k")),
+            ("⌃-", ctrl, character("-")),
+            ("C-M-S-s-k", all, character("k")),
+            ("Ctrl + Left", ctrl, ShortcutKey::from(NamedKey::ArrowLeft)),
+            (
+                "Option+Cmd+K",
+                ModifiersState::ALT | ModifiersState::SUPER,
+                character("k"),
+            ),
+        ] {
+            assert_eq!(
+                Shortcut::parse_logical(s),
+                Ok(Shortcut::new(mods, key)),
+                "{s:?}"
+            );
+        }
+
+        let right_ctrl = SidedModifiers {
+            right: ctrl,
+            ..ctrl.into()
+        };
+        let left_ctrl = SidedModifiers {
+            left: ctrl,
+            ..ctrl.into()
+        };
+        assert_eq!(
+            Shortcut::parse_logical("Right Control + K"),
+            Ok(Shortcut::new(right_ctrl, character("k")))
+        );
+        assert_eq!(
+            Shortcut::parse_logical("left ctrl+k"),
+            Ok(Shortcut::new(left_ctrl, character("k")))
+        );
+    }
+
+    #[test]
+    fn test_sided_shortcut_names_round_trip() {
+        let sided_mods = [
+            ModifiersState::SHIFT,
+            ModifiersState::CONTROL,
+            ModifiersState::ALT,
+            ModifiersState::SUPER,
+        ]
+        .into_iter()
+        .flat_map(|modifier| {
+            let mods = SidedModifiers::from(modifier | ModifiersState::SHIFT);
+            [
+                SidedModifiers {
+                    left: modifier,
+                    ..mods
+                },
+                SidedModifiers {
+                    right: modifier,
+                    ..mods
+                },
+            ]
+        });
+        for platform in Platform::ALL {
+            for mods in sided_mods.clone() {
+                let shortcut = Shortcut::new(mods, character("k"));
+                let name = platform.shortcut_name(&shortcut);
+                // The left modifier keys on macOS have no side in their names.
+                let expected = if platform == Platform::MacOS && !mods.left.is_empty() {
+                    Shortcut::new(mods.state, character("k"))
+                } else {
+                    shortcut
+                };
+                assert_eq!(Shortcut::parse_logical(&name), Ok(expected), "{name:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_shortcut_errors() {
+        use ShortcutParseErrorKind::*;
+
+        assert_eq!(parse_error(""), (MissingKey, 0..0));
+        assert_eq!(parse_error("Ctrl + "), (MissingKey, 7..7));
+        assert_eq!(parse_error("Ctrl + Nonexistent"), (UnknownKey, 7..18));
+        assert_eq!(parse_error("Hyper + K"), (UnknownModifier, 0..5));
+        assert_eq!(parse_error("Ctrl + Hyper + K"), (UnknownModifier, 7..12));
+        assert_eq!(
+            parse_error("Ctrl + Control + K"),
+            (DuplicateModifier, 7..14)
+        );
+        assert_eq!(
+            parse_error("Left Ctrl + Right Ctrl + K"),
+            (DuplicateModifier, 12..22)
+        );
+        assert_eq!(parse_error("⌃⌃K"), (DuplicateModifier, 3..6));
+    }
+}
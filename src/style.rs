@@ -0,0 +1,456 @@
+//! Configurable formatting of modifiers, keys, and shortcuts.
+
+#[cfg(feature = "winit")]
+use winit::keyboard::{Key, KeyCode, ModifiersState, NamedKey, PhysicalKey};
+
+#[cfg(feature = "winit")]
+use crate::{AsModifiersState, KeySequence, Layout, Shortcut, ShortcutKey, SidedModifiers};
+use crate::{Platform, CTRL_STR, SHIFT_STR};
+
+/// How modifiers and special keys are named in a [`ShortcutStyle`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NameStyle {
+    /// Full names, such as "Ctrl" and "Shift".
+    Words,
+    /// Single-letter Emacs-style names: "C" for <key>Ctrl</key>, "M" for
+    /// <key>Alt</key>, "S" for <key>Shift</key>, and "s" for the logo key.
+    Abbreviated,
+    /// macOS-style symbols, such as "⌃" and "⇧". Special keys such as
+    /// <key>Enter</key> and the arrow keys are also named using symbols.
+    Symbols,
+}
+
+/// Style for formatting modifiers, keys, and shortcuts.
+///
+/// Presets are provided for each OS's conventions, along with
+/// [`ShortcutStyle::MACOS_SYMBOLS`] for "⌃⌥⇧⌘K" and [`ShortcutStyle::EMACS`]
+/// for "C-M-S-k". [`ShortcutStyle::NATIVE`] is the preset for the current OS
+/// and produces the same output as [`crate::modifiers_prefix_string()`] and
+/// the [`Display`](std::fmt::Display) implementation of [`Shortcut`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ShortcutStyle {
+    /// Order of modifiers, represented as an ASCII string containing the
+    /// characters `csam` in some order. See [`crate::MODIFIERS_ORDER`].
+    pub modifiers_order: &'static str,
+    /// How modifiers and special keys are named.
+    pub names: NameStyle,
+    /// Name of the <key>Alt</key> modifier when using [`NameStyle::Words`].
+    pub alt_str: &'static str,
+    /// Name of the logo modifier when using [`NameStyle::Words`].
+    pub logo_str: &'static str,
+    /// Separator between modifiers, and between the modifiers and the key.
+    pub separator: &'static str,
+    /// Whether [`ShortcutStyle::modifiers_prefix_string()`] ends with a
+    /// separator so that the key name can be appended directly.
+    pub trailing_separator: bool,
+    /// Separator between the shortcuts in a [`KeySequence`].
+    pub sequence_separator: &'static str,
+    /// Whether single-letter key names are lowercased, as in "C-k".
+    pub lowercase_letters: bool,
+}
+
+impl Default for ShortcutStyle {
+    fn default() -> Self {
+        Self::NATIVE
+    }
+}
+
+impl ShortcutStyle {
+    /// Windows conventions, such as "Ctrl + Shift + Alt + Win + K".
+    pub const WINDOWS: Self = ShortcutStyle {
+        modifiers_order: Platform::Windows.modifiers_order(),
+        names: NameStyle::Words,
+        alt_str: Platform::Windows.alt_str(),
+        logo_str: Platform::Windows.logo_str(),
+        separator: " + ",
+        trailing_separator: true,
+        sequence_separator: ", ",
+        lowercase_letters: false,
+    };
+
+    /// macOS conventions using words, such as "Ctrl + Option + Shift + Cmd +
+    /// K".
+    pub const MACOS: Self = ShortcutStyle {
+        modifiers_order: Platform::MacOS.modifiers_order(),
+        alt_str: Platform::MacOS.alt_str(),
+        logo_str: Platform::MacOS.logo_str(),
+        ..Self::WINDOWS
+    };
+
+    /// macOS menu conventions using symbols, such as "⌃⌥⇧⌘K".
+    pub const MACOS_SYMBOLS: Self = ShortcutStyle {
+        names: NameStyle::Symbols,
+        separator: "",
+        ..Self::MACOS
+    };
+
+    /// Linux conventions, such as "Ctrl + Shift + Alt + Super + K".
+    pub const LINUX: Self = ShortcutStyle {
+        modifiers_order: Platform::Linux.modifiers_order(),
+        alt_str: Platform::Linux.alt_str(),
+        logo_str: Platform::Linux.logo_str(),
+        ..Self::WINDOWS
+    };
+
+    /// Conventions used on web, such as "Ctrl + Shift + Alt + Super + K".
+    pub const WEB: Self = ShortcutStyle {
+        modifiers_order: Platform::Web.modifiers_order(),
+        alt_str: Platform::Web.alt_str(),
+        logo_str: Platform::Web.logo_str(),
+        ..Self::WINDOWS
+    };
+
+    /// Emacs conventions, such as "C-M-S-s-k".
+    pub const EMACS: Self = ShortcutStyle {
+        modifiers_order: "casm",
+        names: NameStyle::Abbreviated,
+        separator: "-",
+        sequence_separator: " ",
+        lowercase_letters: true,
+        ..Self::LINUX
+    };
+
+    /// Conventions for the current OS.
+    pub const NATIVE: Self = Platform::NATIVE.shortcut_style();
+
+    /// All presets, except for [`ShortcutStyle::NATIVE`], which is the same as
+    /// one of the others.
+    pub const PRESETS: [Self; 6] = [
+        Self::WINDOWS,
+        Self::MACOS,
+        Self::MACOS_SYMBOLS,
+        Self::LINUX,
+        Self::WEB,
+        Self::EMACS,
+    ];
+
+    /// Returns the held modifiers in the order of this style along with their
+    /// names, given whether each modifier is held. Modifiers are represented by
+    /// the characters `csam` as in [`ShortcutStyle::modifiers_order`].
+    pub(crate) fn held_modifiers(
+        &self,
+        is_held: impl Fn(char) -> bool,
+    ) -> Vec<(char, &'static str)> {
+        self.modifiers_order
+            .chars()
+            .filter(|&ch| is_held(ch))
+            .filter_map(|ch| {
+                let name = match (ch, self.names) {
+                    ('s', NameStyle::Words) => SHIFT_STR,
+                    ('c', NameStyle::Words) => CTRL_STR,
+                    ('a', NameStyle::Words) => self.alt_str,
+                    ('m', NameStyle::Words) => self.logo_str,
+
+                    ('s', NameStyle::Abbreviated) => "S",
+                    ('c', NameStyle::Abbreviated) => "C",
+                    ('a', NameStyle::Abbreviated) => "M",
+                    ('m', NameStyle::Abbreviated) => "s",
+
+                    ('s', NameStyle::Symbols) => "⇧",
+                    ('c', NameStyle::Symbols) => "⌃",
+                    ('a', NameStyle::Symbols) => "⌥",
+                    ('m', NameStyle::Symbols) => "⌘",
+
+                    _ => return None,
+                };
+                Some((ch, name))
+            })
+            .collect()
+    }
+
+    /// Joins modifier names using the separator, ending with a separator if
+    /// [`ShortcutStyle::trailing_separator`] is set and any modifiers are
+    /// held.
+    pub(crate) fn prefix_string(&self, names: &[impl AsRef<str>]) -> String {
+        let mut ret = String::new();
+        for (i, name) in names.iter().enumerate() {
+            if i > 0 {
+                ret += self.separator;
+            }
+            ret += name.as_ref();
+        }
+        if self.trailing_separator && !ret.is_empty() {
+            ret += self.separator;
+        }
+        ret
+    }
+}
+
+#[cfg(feature = "winit")]
+impl ShortcutStyle {
+    /// Returns the names of the held modifiers in this style, such as
+    /// `["Ctrl", "Shift"]` or `["⌃", "⇧"]`.
+    pub fn modifier_names(&self, mods: impl AsModifiersState) -> Vec<&'static str> {
+        let mods = mods.as_modifiers_state();
+        self.held_modifiers(|ch| modifier_from_char(ch).is_some_and(|m| mods.contains(m)))
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect()
+    }
+
+    /// Returns the names of the held modifiers in this style, such as
+    /// `["Right Control", "Shift"]`.
+    ///
+    /// With [`NameStyle::Words`], modifiers held on only one side of the
+    /// keyboard are given the OS's name for the modifier key on that side, as
+    /// in [`Platform::hardcoded_physical_key_name()`]. Other name styles have
+    /// no side-specific names, so this is the same as
+    /// [`ShortcutStyle::modifier_names()`].
+    pub fn sided_modifier_names(&self, mods: impl Into<SidedModifiers>) -> Vec<String> {
+        self.platform_sided_modifier_names(mods.into(), Platform::NATIVE)
+    }
+
+    /// Returns the names of the held modifiers in this style, using
+    /// `platform`'s names for modifier keys held on only one side of the
+    /// keyboard.
+    pub(crate) fn platform_sided_modifier_names(
+        &self,
+        mods: SidedModifiers,
+        platform: Platform,
+    ) -> Vec<String> {
+        let is_held = |ch| modifier_from_char(ch).is_some_and(|m| mods.state.contains(m));
+        self.held_modifiers(is_held)
+            .into_iter()
+            .map(|(ch, name)| {
+                let sided_name = modifier_from_char(ch)
+                    .filter(|_| self.names == NameStyle::Words)
+                    .and_then(|modifier| sided_modifier_key(modifier, mods))
+                    .and_then(|key_code| {
+                        platform.hardcoded_physical_key_name(PhysicalKey::Code(key_code))
+                    });
+                sided_name.unwrap_or(name).to_string()
+            })
+            .collect()
+    }
+
+    /// Returns a string representing modifiers in this style, such as "Right
+    /// Control + Shift + ", with side-specific names as in
+    /// [`ShortcutStyle::sided_modifier_names()`].
+    pub fn sided_modifiers_prefix_string(&self, mods: impl Into<SidedModifiers>) -> String {
+        self.prefix_string(&self.sided_modifier_names(mods))
+    }
+
+    /// Returns a string representing modifiers in this style, such as "Ctrl +
+    /// Shift + " or "⌃⇧". The string ends with a separator if
+    /// [`ShortcutStyle::trailing_separator`] is set and any modifiers are
+    /// held.
+    pub fn modifiers_prefix_string(&self, mods: impl AsModifiersState) -> String {
+        self.prefix_string(&self.modifier_names(mods))
+    }
+
+    /// Returns a name for a physical key in this style, using the default
+    /// layout.
+    pub fn physical_key_name(&self, physical_key: PhysicalKey) -> String {
+        self.style_key_name(&ShortcutKey::Physical(physical_key), ShortcutKey::to_string)
+    }
+
+    /// Returns a name for a virtual key in this style.
+    pub fn key_name(&self, key: Key) -> String {
+        self.style_key_name(&ShortcutKey::Logical(key), ShortcutKey::to_string)
+    }
+
+    /// Returns a name for a shortcut in this style, using the default layout
+    /// for the name of a physical key.
+    pub fn shortcut_name(&self, shortcut: &Shortcut) -> String {
+        self.style_shortcut_name(shortcut, Platform::NATIVE, ShortcutKey::to_string)
+    }
+
+    /// Returns a name for a key sequence in this style, using the default
+    /// layout for the names of physical keys.
+    pub fn key_sequence_name(&self, sequence: &KeySequence) -> String {
+        self.style_key_sequence_name(sequence, Platform::NATIVE, ShortcutKey::to_string)
+    }
+
+    /// Returns a name for a key in this style, using `key_name` for keys that
+    /// have no symbol.
+    pub(crate) fn style_key_name(
+        &self,
+        key: &ShortcutKey,
+        key_name: impl Fn(&ShortcutKey) -> String,
+    ) -> String {
+        let symbol = match key {
+            ShortcutKey::Physical(PhysicalKey::Code(key_code)) => key_code_symbol(*key_code),
+            ShortcutKey::Logical(Key::Named(named_key)) => named_key_symbol(*named_key),
+            _ => None,
+        };
+        match symbol {
+            Some(symbol) if self.names == NameStyle::Symbols => symbol.to_string(),
+            _ => self.style_letter(key_name(key)),
+        }
+    }
+
+    /// Returns a name for a shortcut in this style, using `platform`'s names
+    /// for side-specific modifiers and `key_name` for keys that have no
+    /// symbol.
+    pub(crate) fn style_shortcut_name(
+        &self,
+        shortcut: &Shortcut,
+        platform: Platform,
+        key_name: impl Fn(&ShortcutKey) -> String,
+    ) -> String {
+        let key_name = self.style_key_name(&shortcut.key, key_name);
+        let mut ret = self
+            .platform_sided_modifier_names(shortcut.mods, platform)
+            .join(self.separator);
+        if !ret.is_empty() {
+            ret += self.separator;
+        }
+        ret + &key_name
+    }
+
+    pub(crate) fn style_key_sequence_name(
+        &self,
+        sequence: &KeySequence,
+        platform: Platform,
+        key_name: impl Fn(&ShortcutKey) -> String,
+    ) -> String {
+        let names: Vec<String> = sequence
+            .strokes()
+            .iter()
+            .map(|shortcut| self.style_shortcut_name(shortcut, platform, &key_name))
+            .collect();
+        names.join(self.sequence_separator)
+    }
+
+    fn style_letter(&self, name: String) -> String {
+        let mut chars = name.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if self.lowercase_letters => c.to_lowercase().collect(),
+            _ => name,
+        }
+    }
+}
+
+/// Returns the modifier represented by one of the characters `csam`.
+#[cfg(feature = "winit")]
+fn modifier_from_char(ch: char) -> Option<ModifiersState> {
+    match ch {
+        's' => Some(ModifiersState::SHIFT),
+        'c' => Some(ModifiersState::CONTROL),
+        'a' => Some(ModifiersState::ALT),
+        'm' => Some(ModifiersState::SUPER),
+        _ => None,
+    }
+}
+
+/// Returns the key for a modifier that is held on only one side of the
+/// keyboard, if it is.
+#[cfg(feature = "winit")]
+fn sided_modifier_key(modifier: ModifiersState, mods: SidedModifiers) -> Option<KeyCode> {
+    let (left, right) = SIDED_MODIFIER_KEYS
+        .iter()
+        .find(|&&(m, _, _)| m == modifier)
+        .map(|&(_, left, right)| (left, right))?;
+    match (mods.left.contains(modifier), mods.right.contains(modifier)) {
+        (true, false) => Some(left),
+        (false, true) => Some(right),
+        _ => None,
+    }
+}
+
+/// Left and right keys for each modifier.
+#[cfg(feature = "winit")]
+const SIDED_MODIFIER_KEYS: [(ModifiersState, KeyCode, KeyCode); 4] = [
+    (
+        ModifiersState::SHIFT,
+        KeyCode::ShiftLeft,
+        KeyCode::ShiftRight,
+    ),
+    (
+        ModifiersState::CONTROL,
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+    ),
+    (ModifiersState::ALT, KeyCode::AltLeft, KeyCode::AltRight),
+    (
+        ModifiersState::SUPER,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ),
+];
+
+#[cfg(feature = "winit")]
+impl Layout {
+    /// Returns a name for a physical key in a particular style, using this
+    /// layout.
+    pub fn physical_key_name_with_style(
+        &self,
+        physical_key: PhysicalKey,
+        style: &ShortcutStyle,
+    ) -> String {
+        style.style_key_name(&ShortcutKey::Physical(physical_key), |key| {
+            self.shortcut_key_name(key)
+        })
+    }
+
+    /// Returns a name for a shortcut in a particular style, using this layout
+    /// for the name of a physical key.
+    pub fn shortcut_name_with_style(&self, shortcut: &Shortcut, style: &ShortcutStyle) -> String {
+        style.style_shortcut_name(shortcut, Platform::NATIVE, |key| {
+            self.shortcut_key_name(key)
+        })
+    }
+
+    /// Returns a name for a key sequence in a particular style, using this
+    /// layout for the names of physical keys.
+    pub fn key_sequence_name_with_style(
+        &self,
+        sequence: &KeySequence,
+        style: &ShortcutStyle,
+    ) -> String {
+        style.style_key_sequence_name(sequence, Platform::NATIVE, |key| {
+            self.shortcut_key_name(key)
+        })
+    }
+
+    fn shortcut_key_name(&self, key: &ShortcutKey) -> String {
+        match key {
+            ShortcutKey::Physical(physical_key) => self.physical_key_name(*physical_key),
+            ShortcutKey::Logical(key) => self.key_name(key.clone()),
+        }
+    }
+}
+
+/// Returns the macOS-style symbol for a special physical key.
+#[cfg(feature = "winit")]
+pub(crate) fn key_code_symbol(key_code: KeyCode) -> Option<&'static str> {
+    match key_code {
+        KeyCode::Enter => Some("↩"),
+        KeyCode::Tab => Some("⇥"),
+        KeyCode::Backspace => Some("⌫"),
+        KeyCode::Delete => Some("⌦"),
+        KeyCode::Escape => Some("⎋"),
+        KeyCode::CapsLock => Some("⇪"),
+        KeyCode::ArrowUp => Some("↑"),
+        KeyCode::ArrowDown => Some("↓"),
+        KeyCode::ArrowLeft => Some("←"),
+        KeyCode::ArrowRight => Some("→"),
+        KeyCode::PageUp => Some("⇞"),
+        KeyCode::PageDown => Some("⇟"),
+        KeyCode::Home => Some("↖"),
+        KeyCode::End => Some("↘"),
+        _ => None,
+    }
+}
+
+/// Returns the macOS-style symbol for a special virtual key.
+#[cfg(feature = "winit")]
+pub(crate) fn named_key_symbol(named_key: NamedKey) -> Option<&'static str> {
+    match named_key {
+        NamedKey::Enter => Some("↩"),
+        NamedKey::Tab => Some("⇥"),
+        NamedKey::Backspace => Some("⌫"),
+        NamedKey::Delete => Some("⌦"),
+        NamedKey::Escape => Some("⎋"),
+        NamedKey::CapsLock => Some("⇪"),
+        NamedKey::ArrowUp => Some("↑"),
+        NamedKey::ArrowDown => Some("↓"),
+        NamedKey::ArrowLeft => Some("←"),
+        NamedKey::ArrowRight => Some("→"),
+        NamedKey::PageUp => Some("⇞"),
+        NamedKey::PageDown => Some("⇟"),
+        NamedKey::Home => Some("↖"),
+        NamedKey::End => Some("↘"),
+        _ => None,
+    }
+}
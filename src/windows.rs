@@ -1,26 +1,84 @@
-use winit::keyboard::{NamedKey, PhysicalKey};
+#[cfg(feature = "winit")]
+use winit::keyboard::{NativeKey, NativeKeyCode, PhysicalKey};
+#[cfg(feature = "winit")]
 use winit::platform::scancode::PhysicalKeyExtScancode;
 
-pub const ALT_STR: &str = "Alt";
-pub const LOGO_STR: &str = "Win";
-pub const MODIFIERS_ORDER: &str = "csam"; // Ctrl + Shift + Alt + Meta
+use crate::{KeyNamesError, Level};
 
-pub fn os_specific_key_name(key: NamedKey) -> Option<&'static str> {
-    match key {
-        NamedKey::AltGraph => Some("AltGr"),
-        NamedKey::ArrowDown => Some("Down"),
-        NamedKey::ArrowLeft => Some("Left"),
-        NamedKey::ArrowRight => Some("Right"),
-        NamedKey::ArrowUp => Some("Up"),
-        NamedKey::Super => Some("Windows"),
-        _ => None,
-    }
+#[cfg(feature = "winit")]
+pub fn native_key_code(sc: u32) -> Option<NativeKeyCode> {
+    u16::try_from(sc).ok().map(NativeKeyCode::Windows)
+}
+
+#[cfg(feature = "winit")]
+pub fn native_key(sc: u32) -> Option<NativeKey> {
+    u16::try_from(sc).ok().map(NativeKey::Windows)
 }
 
-pub fn try_physical_key_name(physical_key: PhysicalKey) -> Option<String> {
-    physical_key
-        .to_scancode()
-        .map(|sc| scancode_name(sc as u16))
+#[derive(Debug, Default, Clone)]
+pub struct Layout;
+
+impl Layout {
+    pub fn new() -> Result<Self, KeyNamesError> {
+        Ok(Layout)
+    }
+
+    pub fn try_scancode_name(&self, sc: u32) -> Option<String> {
+        u16::try_from(sc).ok().map(scancode_name)
+    }
+
+    #[cfg(feature = "winit")]
+    pub fn try_physical_key_name(&self, physical_key: PhysicalKey) -> Option<String> {
+        physical_key
+            .to_scancode()
+            .map(|sc| scancode_name(sc as u16))
+    }
+
+    #[cfg(feature = "winit")]
+    pub fn physical_key_for_char(&self, c: char) -> Option<(PhysicalKey, Level)> {
+        let (sc, level) = self.scancode_for_char(c)?;
+        Some((PhysicalKey::from_scancode(sc), level))
+    }
+
+    pub fn scancode_for_char(&self, c: char) -> Option<(u32, Level)> {
+        if c.len_utf16() != 1 {
+            return None;
+        }
+        let mut utf16 = [0_u16; 1];
+        c.encode_utf16(&mut utf16);
+
+        // SAFETY: `VkKeyScanW()` returns -1 if it fails.
+        let result = unsafe { winapi::um::winuser::VkKeyScanW(utf16[0]) };
+        if result == -1 {
+            return None;
+        }
+
+        // The low byte is the virtual key code and the high byte is the shift
+        // state: 1 for Shift, 2 for Ctrl, and 4 for Alt.
+        let vk = (result & 0xFF) as u32;
+        let level = match (result >> 8) & 0xFF {
+            0 => Level::Base,
+            1 => Level::Shift,
+            6 => Level::AltGr, // Ctrl + Alt
+            7 => Level::ShiftAltGr,
+            _ => return None,
+        };
+
+        // SAFETY: `MapVirtualKeyW()` returns 0 if it fails.
+        let sc = unsafe {
+            winapi::um::winuser::MapVirtualKeyW(vk, winapi::um::winuser::MAPVK_VK_TO_VSC_EX)
+        };
+        if sc == 0 {
+            return None;
+        }
+
+        Some((sc, level))
+    }
+
+    #[cfg(feature = "winit")]
+    pub fn unidentified_physical_keys(&self) -> Vec<PhysicalKey> {
+        vec![]
+    }
 }
 
 fn scancode_name(sc: u16) -> String {